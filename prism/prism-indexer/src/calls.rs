@@ -0,0 +1,148 @@
+//! Call site extraction
+//!
+//! Calls are recorded with the callee as written, the simple name that a
+//! symbol lookup would use and the receiver it is called on, so retrieval
+//! can expand a function to its callees and callers.
+
+use crate::extractor::{function_name, node_text};
+use crate::types::CallSite;
+use tree_sitter::Node;
+
+/// Nodes that define a function of their own
+const FUNCTION_NODES: &[&str] = &[
+    "function_declaration",
+    "function_definition",
+    "method_definition",
+    "arrow_function",
+    "function_expression",
+    "function_item",
+    "generator_function_declaration",
+    "generator_function",
+    "method_declaration",
+    "constructor_declaration",
+];
+
+/// Every call in a subtree, in source order
+pub(crate) fn extract_calls(node: &Node, source: &str) -> Vec<CallSite> {
+    let mut calls = Vec::new();
+    collect_calls(node, source, false, &mut calls);
+    calls
+}
+
+/// Calls made by a function itself; named nested functions record their own
+/// calls, while anonymous callbacks (`items.map(x => f(x))`) belong to the
+/// function that defines them
+pub(crate) fn function_calls(node: &Node, source: &str) -> Vec<CallSite> {
+    let mut calls = Vec::new();
+    if let Some(body) = node.child_by_field_name("body") {
+        // Expression-bodied arrows: `x => f(x)`
+        calls.extend(call_site(&body, source));
+        collect_calls(&body, source, true, &mut calls);
+    }
+    calls
+}
+
+/// Calls whose line lies within the given 1-based, inclusive lines
+pub(crate) fn calls_in_lines(calls: &[CallSite], start_line: usize, end_line: usize) -> Vec<CallSite> {
+    calls
+        .iter()
+        .filter(|call| call.line >= start_line && call.line <= end_line)
+        .cloned()
+        .collect()
+}
+
+fn collect_calls(node: &Node, source: &str, skip_named_functions: bool, calls: &mut Vec<CallSite>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if skip_named_functions
+            && FUNCTION_NODES.contains(&child.kind())
+            && function_name(&child, source).is_some()
+        {
+            continue;
+        }
+
+        if let Some(call) = call_site(&child, source) {
+            calls.push(call);
+        }
+        // Arguments and chained receivers hold further calls: `a(b()).c()`
+        collect_calls(&child, source, skip_named_functions, calls);
+    }
+}
+
+/// Describe a call node: `call_expression` (JS/TS, Rust, Go, C/C++),
+/// Python `call` and Java `method_invocation`
+fn call_site(node: &Node, source: &str) -> Option<CallSite> {
+    let (callee, name, receiver) = match node.kind() {
+        "call_expression" | "call" => {
+            let function = node.child_by_field_name("function")?;
+            let (name, receiver) = split_callee(&function, source);
+            (node_text(&function, source), name, receiver)
+        }
+        "method_invocation" => {
+            let name = node.child_by_field_name("name").map(|n| node_text(&n, source))?;
+            let receiver = node.child_by_field_name("object").map(|o| node_text(&o, source));
+            let end = node.child_by_field_name("arguments").map_or(node.end_byte(), |a| a.start_byte());
+            (source[node.start_byte()..end].trim().to_string(), name, receiver)
+        }
+        _ => return None,
+    };
+
+    // `await f()` in JS/Python, `f().await` in Rust
+    let is_awaited = node
+        .parent()
+        .is_some_and(|parent| parent.kind() == "await_expression" || parent.kind() == "await");
+
+    Some(CallSite {
+        callee,
+        name,
+        receiver,
+        line: node.start_position().row + 1,
+        column: node.start_position().column,
+        is_awaited,
+    })
+}
+
+/// Split a callee into its simple name and the receiver or path before it
+fn split_callee(function: &Node, source: &str) -> (String, Option<String>) {
+    let parts = match function.kind() {
+        "member_expression" => Some(("object", "property")),
+        "attribute" => Some(("object", "attribute")),
+        "selector_expression" => Some(("operand", "field")),
+        "scoped_identifier" => Some(("path", "name")),
+        "qualified_identifier" => Some(("scope", "name")),
+        // Rust `x.f()` uses `value`, C `x->f()` uses `argument`
+        "field_expression" if function.child_by_field_name("value").is_some() => Some(("value", "field")),
+        "field_expression" => Some(("argument", "field")),
+        // `parse::<T>()`, `make<T>()`
+        "generic_function" => {
+            return function
+                .child_by_field_name("function")
+                .map_or_else(|| (node_text(function, source), None), |f| split_callee(&f, source));
+        }
+        "template_function" => {
+            return function
+                .child_by_field_name("name")
+                .map_or_else(|| (node_text(function, source), None), |f| split_callee(&f, source));
+        }
+        _ => None,
+    };
+
+    let Some((receiver_field, name_field)) = parts else {
+        return (node_text(function, source), None);
+    };
+
+    let receiver = function.child_by_field_name(receiver_field).map(|r| node_text(&r, source));
+    match function.child_by_field_name(name_field) {
+        // C++ `a::b::c` nests the rest of the path in the name
+        Some(name) if name.kind() == "qualified_identifier" || name.kind() == "template_function" => {
+            let (name, inner) = split_callee(&name, source);
+            let receiver = match (receiver, inner) {
+                (Some(outer), Some(inner)) => Some(format!("{}::{}", outer, inner)),
+                (receiver, inner) => receiver.or(inner),
+            };
+            (name, receiver)
+        }
+        Some(name) => (node_text(&name, source), receiver),
+        None => (node_text(function, source), receiver),
+    }
+}
@@ -1,6 +1,17 @@
-use crate::types::{CodeChunk, FunctionInfo, ClassInfo, ImportInfo};
+use crate::calls::calls_in_lines;
+use crate::error::{PrismError, Result};
+use crate::extractor::FileSyntax;
+use crate::identifiers::identifiers_in_lines;
+use crate::identity::{assign_ids, assign_part_ids};
+use crate::language::get_language_config;
+use crate::recovery::{RecoveredBlock, Recovery};
+use crate::symbols::{qualify, scope_separator};
+use crate::tokenizer::{default_tokenizer, Tokenizer};
+use crate::types::{
+    ChunkingOptions, ClassInfo, CodeChunk, Documentation, FunctionInfo, LineRange, NestedSymbols, SymbolInfo,
+    SymbolKind,
+};
 use tree_sitter::Node;
-use uuid::Uuid;
 
 /// Default chunk size (in tokens)
 pub const DEFAULT_CHUNK_SIZE: usize = 512;
@@ -14,127 +25,399 @@ pub const MAX_LINES_PER_CHUNK: usize = 200;
 /// Minimum lines for a chunk (unless it's the only content)
 pub const MIN_LINES_PER_CHUNK: usize = 5;
 
+/// C/C++ preprocessor constructs that must not be split across chunks
+const PREPROCESSOR_BLOCKS: &[&str] = &[
+    "preproc_if",
+    "preproc_ifdef",
+    "preproc_def",
+    "preproc_function_def",
+];
+
+/// Chunking options with the defaults of a language filled in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ChunkSettings {
+    pub(crate) target_tokens: usize,
+    pub(crate) max_tokens: usize,
+    pub(crate) overlap_tokens: usize,
+    pub(crate) min_lines: usize,
+    pub(crate) max_lines: usize,
+    pub(crate) include_imports: bool,
+    pub(crate) include_docs: bool,
+    pub(crate) nested: NestedSymbols,
+}
+
+impl ChunkSettings {
+    /// Fill in the language defaults and check that the options fit together
+    pub(crate) fn resolve(options: &ChunkingOptions, language: &str) -> Result<Self> {
+        let defaults = Self::defaults(language);
+        let target_tokens = options.target_tokens.unwrap_or(defaults.target_tokens);
+        let max_tokens = options.max_tokens.unwrap_or(defaults.max_tokens.max(target_tokens));
+        let max_lines = options.max_lines.unwrap_or(defaults.max_lines);
+        let min_lines = options.min_lines.unwrap_or(MIN_LINES_PER_CHUNK.min(max_lines));
+
+        let invalid = |message: String| Err(PrismError::InvalidOptions(message));
+        if target_tokens == 0 {
+            return invalid("target_tokens must be greater than 0".to_string());
+        }
+        if max_tokens < target_tokens {
+            return invalid(format!("max_tokens ({}) must be at least target_tokens ({})", max_tokens, target_tokens));
+        }
+        let overlap_tokens = options.overlap_tokens.unwrap_or(DEFAULT_OVERLAP.min(target_tokens / 4));
+        if overlap_tokens >= target_tokens {
+            return invalid(format!("overlap_tokens ({}) must be less than target_tokens ({})", overlap_tokens, target_tokens));
+        }
+        if max_lines == 0 {
+            return invalid("max_lines must be greater than 0".to_string());
+        }
+        if min_lines > max_lines {
+            return invalid(format!("min_lines ({}) must not exceed max_lines ({})", min_lines, max_lines));
+        }
+
+        Ok(ChunkSettings {
+            target_tokens,
+            max_tokens,
+            overlap_tokens,
+            min_lines,
+            max_lines,
+            include_imports: options.include_imports.unwrap_or(defaults.include_imports),
+            include_docs: options.include_docs.unwrap_or(defaults.include_docs),
+            nested: options.nested.unwrap_or(defaults.nested),
+        })
+    }
+
+    /// The defaults of a language, which need no checking
+    pub(crate) fn defaults(language: &str) -> Self {
+        let config = get_language_config(language);
+        let target_tokens = config.preferred_chunk_size;
+        ChunkSettings {
+            target_tokens,
+            max_tokens: MAX_CHUNK_SIZE.max(target_tokens),
+            overlap_tokens: DEFAULT_OVERLAP.min(target_tokens / 4),
+            min_lines: MIN_LINES_PER_CHUNK.min(config.max_lines),
+            max_lines: config.max_lines,
+            include_imports: config.include_imports,
+            include_docs: config.include_docs,
+            nested: NestedSymbols::default(),
+        }
+    }
+}
+
 /// Chunk code into semantic units at function/class level
 pub fn chunk_code(root: &Node, source: &str, language: &str) -> Vec<CodeChunk> {
-    let mut chunks = Vec::new();
+    chunk_code_with_mode(root, source, language, NestedSymbols::default())
+}
 
-    // Extract imports first (for context)
-    let imports = crate::extractor::extract_imports(root, source);
+/// Chunk code, choosing whether nested definitions are chunked separately
+pub fn chunk_code_with_mode(root: &Node, source: &str, language: &str, nested: NestedSymbols) -> Vec<CodeChunk> {
+    let settings = ChunkSettings { nested, ..ChunkSettings::defaults(language) };
+    let syntax = FileSyntax::extract(root, source, language);
+    chunk_file(root, source, language, "", &settings, default_tokenizer().as_ref(), &syntax)
+}
 
-    // Extract functions and classes
-    let functions = crate::extractor::extract_functions(root, source);
-    let classes = crate::extractor::extract_classes(root, source);
+/// Chunk code with the given options, after validating them
+pub fn chunk_code_with_options(
+    root: &Node,
+    source: &str,
+    language: &str,
+    options: &ChunkingOptions,
+) -> Result<Vec<CodeChunk>> {
+    let settings = ChunkSettings::resolve(options, language)?;
+    let syntax = FileSyntax::extract(root, source, language);
+    Ok(chunk_file(root, source, language, "", &settings, default_tokenizer().as_ref(), &syntax))
+}
+
+/// Chunk the code of a file; its path is part of every chunk id
+pub(crate) fn chunk_file(
+    root: &Node,
+    source: &str,
+    language: &str,
+    file_path: &str,
+    settings: &ChunkSettings,
+    tokenizer: &dyn Tokenizer,
+    syntax: &FileSyntax,
+) -> Vec<CodeChunk> {
+    let mut chunks = Vec::new();
+
+    // Imports are attached to every chunk for context
+    let imports = if settings.include_imports { syntax.imports.clone() } else { Vec::new() };
 
     // Strategy: Create chunks at function/class level
     // 1. Each top-level function becomes a chunk
     // 2. Each class becomes a chunk (with methods as sub-chunks if too large)
     // 3. Remaining top-level code becomes chunks
 
-    let source_lines: Vec<&str> =.source.lines().collect();
+    let source_lines: Vec<&str> = source.lines().collect();
 
     // Track which lines are already covered
     let mut covered_lines = vec![false; source_lines.len()];
 
+    // Preprocessor blocks (C/C++ only) are kept intact where possible
+    let mut preprocessor_blocks = Vec::new();
+    collect_preprocessor_blocks(root, &mut preprocessor_blocks);
+
+    let include_nested = settings.nested == NestedSymbols::Include;
+    let chunked_classes: Vec<&ClassInfo> = syntax
+        .classes
+        .iter()
+        .filter(|class| include_nested || class.depth == 0)
+        .collect();
+    let chunked_functions: Vec<&FunctionInfo> = syntax
+        .functions
+        .iter()
+        .filter(|func| include_nested || func.depth == 0)
+        .collect();
+
+    // In files with syntax errors, definitions can run into code the parser
+    // swallowed, and declarations it lost are recovered from the text
+    let recovery = Recovery::new(root, source, language);
+    let trim = |span: (usize, usize)| recovery.as_ref().map_or(span, |recovery| recovery.trim(span));
+
+    let definitions: Vec<(usize, usize)> = chunked_classes
+        .iter()
+        .map(|class| (class.start_line, class.end_line))
+        .chain(chunked_functions.iter().map(|func| (func.start_line, func.end_line)))
+        .collect();
+
     // Process classes first
-    for class in &classes {
-        let chunk = create_class_chunk(class, source, language, &imports);
+    for class in chunked_classes {
+        let span = enclosing_preprocessor_block(
+            (class.start_line, class.end_line),
+            &preprocessor_blocks,
+            &definitions,
+            settings.max_lines,
+        );
+        let mut chunk = create_class_chunk(class, trim(span), source, language, settings.include_docs);
+        clamp_to_chunk(&mut chunk);
         mark_lines_covered(&mut covered_lines, chunk.start_line, chunk.end_line);
         chunks.push(chunk);
     }
 
-    // Process standalone functions
-    for func in &functions {
-        // Skip if this function is inside a class (already covered)
-        if is_inside_class(func, &classes) {
-            continue;
-        }
-
-        let chunk = create_function_chunk(func, source, language, &imports);
+    // Process functions (nested ones are already covered unless requested)
+    for func in chunked_functions {
+        let span = enclosing_preprocessor_block(
+            (func.start_line, func.end_line),
+            &preprocessor_blocks,
+            &definitions,
+            settings.max_lines,
+        );
+        let mut chunk = create_function_chunk(func, trim(span), source, language, settings.include_docs);
+        clamp_to_chunk(&mut chunk);
         mark_lines_covered(&mut covered_lines, chunk.start_line, chunk.end_line);
         chunks.push(chunk);
     }
 
+    // Declarations the parser lost to syntax errors
+    if let Some(recovery) = &recovery {
+        for block in recovery.blocks(&covered_lines) {
+            let chunk = create_recovered_chunk(&block, source, language);
+            mark_lines_covered(&mut covered_lines, chunk.start_line, chunk.end_line);
+            chunks.push(chunk);
+        }
+    }
+
     // Create chunks for uncovered top-level code
-    let uncovered_chunks = create_uncovered_chunks(
+    let mut uncovered_chunks = create_uncovered_chunks(
         &covered_lines,
+        &preprocessor_blocks,
+        &syntax.symbols,
         source,
         language,
-        &imports,
+        recovery.as_ref(),
+        settings,
     );
+    overlap_sections(&mut uncovered_chunks, &source_lines, settings.overlap_tokens, tokenizer);
     chunks.extend(uncovered_chunks);
 
+    for chunk in &mut chunks {
+        chunk.tokens = tokenizer.count_tokens(&chunk.text);
+    }
+    assign_ids(&mut chunks, file_path);
+
+    // Split chunks too large for the token budget
+    chunks = chunks
+        .into_iter()
+        .flat_map(|chunk| {
+            if chunk.tokens > settings.max_tokens {
+                split_large_chunk_with_overlap(&chunk, settings.target_tokens, settings.overlap_tokens, tokenizer)
+            } else {
+                vec![chunk]
+            }
+        })
+        .collect();
+
+    // Aggregate the imports, calls and identifiers of each chunk
+    for chunk in &mut chunks {
+        chunk.imports = imports.clone();
+        chunk.calls = calls_in_lines(&syntax.calls, chunk.start_line, chunk.end_line);
+        (chunk.defines, chunk.references) =
+            identifiers_in_lines(&syntax.identifiers, chunk.start_line, chunk.end_line);
+        chunk.has_errors = recovery
+            .as_ref()
+            .is_some_and(|recovery| recovery.has_errors(chunk.start_line, chunk.end_line));
+    }
+
+    // Flag test code and link it to the symbols it exercises
+    if let Some(tests) = &syntax.tests {
+        for chunk in chunks.iter_mut().filter(|chunk| tests.is_test_lines(chunk.start_line, chunk.end_line)) {
+            chunk.is_test = true;
+            chunk.test_subjects =
+                tests.subjects(chunk.start_line, chunk.end_line, &syntax.calls, &syntax.identifiers);
+        }
+    }
+
     chunks
 }
 
-/// Create a chunk for a class
+/// Create a chunk for a class spanning `span` (1-based, inclusive lines)
 fn create_class_chunk(
     class: &ClassInfo,
+    span: (usize, usize),
     source: &str,
     language: &str,
-    imports: &[ImportInfo],
+    include_docs: bool,
 ) -> CodeChunk {
     let source_lines: Vec<&str> = source.lines().collect();
 
-    // Extract class content with surrounding context
-    let start_idx = class.start_line.saturating_sub(1);
-    let end_idx = class.end_line.min(source_lines.len());
+    // Extract class content along with its doc comment
+    let start_line = if include_docs { documented_start(span.0, &class.documentation) } else { span.0 };
+    let start_idx = start_line.saturating_sub(1);
+    let end_idx = span.1.min(source_lines.len());
 
     let text = source_lines[start_idx..end_idx].join("\n");
-    let token_count = estimate_tokens(&text);
+    let dependencies = extract_dependencies(&text);
 
     CodeChunk {
-        id: Uuid::new_v4().to_string(),
+        id: String::new(),
+        stable_id: String::new(),
+        content_hash: String::new(),
         text,
-        start_line: class.start_line,
-        end_line: class.end_line,
-        tokens: token_count,
+        start_line,
+        end_line: span.1,
+        tokens: 0,
         language: language.to_string(),
+        kind: Some(class.kind),
+        qualified_name: Some(qualified_name(&class.name, class.enclosing_symbol.as_deref(), language)),
+        documentation: class.documentation.clone(),
         functions: class.methods.clone(),
         classes: vec![class.clone()],
-        imports: imports.to_vec(),
-        dependencies: extract_dependencies(&text),
+        imports: Vec::new(),
+        dependencies,
+        calls: Vec::new(),
+        defines: Vec::new(),
+        references: Vec::new(),
+        is_test: false,
+        test_subjects: Vec::new(),
+        has_errors: false,
+        overlap: None,
     }
 }
 
-/// Create a chunk for a function
+/// Create a chunk for a function spanning `span` (1-based, inclusive lines)
 fn create_function_chunk(
     func: &FunctionInfo,
+    span: (usize, usize),
     source: &str,
     language: &str,
-    imports: &[ImportInfo],
+    include_docs: bool,
 ) -> CodeChunk {
     let source_lines: Vec<&str> = source.lines().collect();
 
-    // Extract function content with surrounding context (comments, types)
-    let start_idx = func.start_line.saturating_sub(1);
-    let end_idx = func.end_line.min(source_lines.len());
-
-    // Look for preceding comments
-    let context_start = find_preceding_context(&source_lines, start_idx);
+    // Extract function content along with its doc comment
+    let start_line = if include_docs { documented_start(span.0, &func.documentation) } else { span.0 };
+    let start_idx = start_line.saturating_sub(1);
+    let end_idx = span.1.min(source_lines.len());
 
-    let text = source_lines[context_start..end_idx].join("\n");
-    let token_count = estimate_tokens(&text);
+    let text = source_lines[start_idx..end_idx].join("\n");
+    let dependencies = extract_dependencies(&text);
 
     CodeChunk {
-        id: Uuid::new_v4().to_string(),
+        id: String::new(),
+        stable_id: String::new(),
+        content_hash: String::new(),
         text,
-        start_line: context_start + 1,
-        end_line: func.end_line,
-        tokens: token_count,
+        start_line,
+        end_line: span.1,
+        tokens: 0,
         language: language.to_string(),
+        kind: Some(func.kind),
+        qualified_name: Some(qualified_name(&func.name, func.enclosing_symbol.as_deref(), language)),
+        documentation: func.documentation.clone(),
         functions: vec![func.clone()],
         classes: Vec::new(),
-        imports: imports.to_vec(),
+        imports: Vec::new(),
+        dependencies,
+        calls: Vec::new(),
+        defines: Vec::new(),
+        references: Vec::new(),
+        is_test: false,
+        test_subjects: Vec::new(),
+        has_errors: false,
+        overlap: None,
+    }
+}
+
+/// Create a chunk for a declaration recovered from a region with syntax errors
+fn create_recovered_chunk(block: &RecoveredBlock, source: &str, language: &str) -> CodeChunk {
+    let source_lines: Vec<&str> = source.lines().collect();
+    let text = source_lines[block.start_line - 1..block.end_line.min(source_lines.len())].join("\n");
+
+    CodeChunk {
+        id: String::new(),
+        stable_id: String::new(),
+        content_hash: String::new(),
+        tokens: 0,
         dependencies: extract_dependencies(&text),
+        text,
+        start_line: block.start_line,
+        end_line: block.end_line,
+        language: language.to_string(),
+        kind: block.kind,
+        qualified_name: None,
+        documentation: None,
+        functions: Vec::new(),
+        classes: Vec::new(),
+        imports: Vec::new(),
+        calls: Vec::new(),
+        defines: Vec::new(),
+        references: Vec::new(),
+        is_test: false,
+        test_subjects: Vec::new(),
+        has_errors: true,
+        overlap: None,
+    }
+}
+
+/// Qualified name of a definition, as in the symbol outline
+fn qualified_name(name: &str, enclosing_symbol: Option<&str>, language: &str) -> String {
+    qualify(enclosing_symbol.unwrap_or(""), name, scope_separator(language))
+}
+
+/// Drop the parts of a definition's metadata that lie past a chunk cut
+/// short by error recovery
+fn clamp_to_chunk(chunk: &mut CodeChunk) {
+    let end_line = chunk.end_line;
+    chunk.functions.retain(|func| func.start_line <= end_line);
+    for func in &mut chunk.functions {
+        func.end_line = func.end_line.min(end_line);
+    }
+    for class in &mut chunk.classes {
+        class.end_line = class.end_line.min(end_line);
+        class.methods.retain(|method| method.start_line <= end_line);
     }
 }
 
 /// Create chunks for uncovered top-level code
+///
+/// Short sections are dropped unless they touch a syntax error, so that
+/// code the parser lost still gets indexed.
 fn create_uncovered_chunks(
     covered_lines: &[bool],
+    preprocessor_blocks: &[(usize, usize)],
+    symbols: &[SymbolInfo],
     source: &str,
     language: &str,
-    imports: &[ImportInfo],
+    recovery: Option<&Recovery>,
+    settings: &ChunkSettings,
 ) -> Vec<CodeChunk> {
     let mut chunks = Vec::new();
     let source_lines: Vec<&str> = source.lines().collect();
@@ -158,28 +441,50 @@ fn create_uncovered_chunks(
 
         // Create chunk for this section
         let lines_count = end_idx - start_idx;
+        let has_errors = recovery.is_some_and(|recovery| recovery.has_errors(start_idx + 1, end_idx));
 
-        if lines_count >= MIN_LINES_PER_CHUNK {
+        if lines_count >= settings.min_lines || has_errors {
             // Split large sections into smaller chunks
-            let chunk_size = MAX_LINES_PER_CHUNK.min(lines_count);
-            for i in (start_idx..end_idx).step_by(chunk_size) {
-                let chunk_end = (i + chunk_size).min(end_idx);
+            let mut i = start_idx;
+            while i < end_idx {
+                let chunk_end = find_split_point(
+                    i,
+                    (i + settings.max_lines).min(end_idx),
+                    end_idx,
+                    preprocessor_blocks,
+                    settings.max_lines,
+                );
                 let text = source_lines[i..chunk_end].join("\n");
 
-                if text.trim().len() >= MIN_CHUNK_SIZE {
+                if text.trim().len() >= MIN_CHUNK_SIZE || (has_errors && !text.trim().is_empty()) {
+                    let dependencies = extract_dependencies(&text);
                     chunks.push(CodeChunk {
-                        id: Uuid::new_v4().to_string(),
+                        id: String::new(),
+                        stable_id: String::new(),
+                        content_hash: String::new(),
                         text,
                         start_line: i + 1,
                         end_line: chunk_end,
-                        tokens: estimate_tokens(&text),
+                        tokens: 0,
                         language: language.to_string(),
+                        kind: first_symbol_kind(symbols, i + 1, chunk_end),
+                        qualified_name: None,
+                        documentation: None,
                         functions: Vec::new(),
                         classes: Vec::new(),
-                        imports: imports.to_vec(),
-                        dependencies: extract_dependencies(&text),
+                        imports: Vec::new(),
+                        dependencies,
+                        calls: Vec::new(),
+                        defines: Vec::new(),
+                        references: Vec::new(),
+                        is_test: false,
+                        test_subjects: Vec::new(),
+                        has_errors: false,
+                        overlap: None,
                     });
                 }
+
+                i = chunk_end;
             }
         }
 
@@ -189,23 +494,92 @@ fn create_uncovered_chunks(
     chunks
 }
 
-/// Find preceding context (comments, blank lines) for a chunk
-fn find_preceding_context(lines: &[&str], start_idx: usize) -> usize {
-    let mut context_start = start_idx;
+/// Choose where to end a line-based chunk starting at `start_idx`
+///
+/// `end_idx` is the preferred (exclusive) end. If it would cut through a
+/// preprocessor block that fits in a single chunk, the chunk ends right
+/// before that block instead.
+fn find_split_point(
+    start_idx: usize,
+    end_idx: usize,
+    section_end: usize,
+    preprocessor_blocks: &[(usize, usize)],
+    max_lines: usize,
+) -> usize {
+    preprocessor_blocks
+        .iter()
+        .map(|&(start_line, end_line)| (start_line - 1, end_line))
+        .filter(|&(block_start, block_end)| {
+            block_start > start_idx
+                && block_start < end_idx
+                && block_end > end_idx
+                && block_end <= section_end
+                && block_end - block_start <= max_lines
+        })
+        .map(|(block_start, _)| block_start)
+        .min()
+        .unwrap_or(end_idx)
+}
 
-    // Look back up to 3 lines for context
-    for i in (0..3).rev() {
-        if start_idx > i {
-            let line = lines[start_idx - i - 1];
-            if line.trim().is_empty() || line.trim().starts_with("//") || line.trim().starts_with("*") {
-                context_start = start_idx - i - 1;
-            } else {
-                break;
-            }
-        }
+/// Collect line ranges (1-based, inclusive) of preprocessor blocks
+fn collect_preprocessor_blocks(node: &Node, blocks: &mut Vec<(usize, usize)>) {
+    if PREPROCESSOR_BLOCKS.contains(&node.kind()) {
+        let start = node.start_position();
+        let end = node.end_position();
+
+        // Directives include their trailing newline
+        let end_line = if end.column == 0 && end.row > start.row {
+            end.row
+        } else {
+            end.row + 1
+        };
+        blocks.push((start.row + 1, end_line));
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_preprocessor_blocks(&child, blocks);
     }
+}
+
+/// Widen a definition's span to the outermost preprocessor block that
+/// wraps it alone, e.g. a function guarded by `#ifdef DEBUG ... #endif`
+fn enclosing_preprocessor_block(
+    span: (usize, usize),
+    preprocessor_blocks: &[(usize, usize)],
+    definitions: &[(usize, usize)],
+    max_lines: usize,
+) -> (usize, usize) {
+    preprocessor_blocks
+        .iter()
+        .filter(|&&(start, end)| {
+            start <= span.0
+                && end >= span.1
+                && end - start < max_lines
+                && definitions
+                    .iter()
+                    .all(|&(d_start, d_end)| (d_start, d_end) == span || d_end < start || d_start > end)
+        })
+        .min_by_key(|&&(start, _)| start)
+        .copied()
+        .unwrap_or(span)
+}
 
-    context_start
+/// First line of a definition including its doc comment
+///
+/// Python docstrings sit inside the body, so they never move the start.
+fn documented_start(start_line: usize, documentation: &Option<Documentation>) -> usize {
+    documentation
+        .as_ref()
+        .map_or(start_line, |doc| doc.start_line.min(start_line))
+}
+
+/// Kind of the first symbol that lies entirely within the given lines
+fn first_symbol_kind(symbols: &[SymbolInfo], start_line: usize, end_line: usize) -> Option<SymbolKind> {
+    symbols
+        .iter()
+        .find(|symbol| symbol.start_line >= start_line && symbol.end_line <= end_line)
+        .map(|symbol| symbol.kind)
 }
 
 /// Mark lines as covered
@@ -218,16 +592,6 @@ fn mark_lines_covered(covered_lines: &mut [bool], start_line: usize, end_line: u
     }
 }
 
-/// Check if a function is inside a class
-fn is_inside_class(func: &FunctionInfo, classes: &[ClassInfo]) -> bool {
-    for class in classes {
-        if func.start_line >= class.start_line && func.end_line <= class.end_line {
-            return true;
-        }
-    }
-    false
-}
-
 /// Extract dependencies from code text
 fn extract_dependencies(text: &str) -> Vec<String> {
     let mut deps = Vec::new();
@@ -235,7 +599,8 @@ fn extract_dependencies(text: &str) -> Vec<String> {
     // Simple extraction of import statements
     for line in text.lines() {
         let line = line.trim();
-        if line.starts_with("import ") || line.starts_with("use ") || line.starts_with("require(") {
+        if line.starts_with("import ") || line.starts_with("use ") || line.starts_with("require(") ||
+            line.starts_with("#include") {
             deps.push(line.to_string());
         }
     }
@@ -243,77 +608,144 @@ fn extract_dependencies(text: &str) -> Vec<String> {
     deps
 }
 
-/// Estimate token count from text
-fn estimate_tokens(text: &str) -> usize {
-    if text.is_empty() {
-        return 0;
-    }
-
-    // More accurate token estimation
-    // Average: ~4 characters per token for code
-    let count = text.len() / 4;
+/// Split large chunks into smaller pieces
+pub fn split_large_chunk(chunk: &CodeChunk, target_size: usize) -> Vec<CodeChunk> {
+    split_large_chunk_with(chunk, target_size, default_tokenizer().as_ref())
+}
 
-    // Minimum of 1 token for non-empty text
-    count.max(1)
+/// Split large chunks into pieces of at most `target_size` tokens as counted
+/// by `tokenizer`, where single lines allow
+pub fn split_large_chunk_with(chunk: &CodeChunk, target_size: usize, tokenizer: &dyn Tokenizer) -> Vec<CodeChunk> {
+    split_large_chunk_with_overlap(chunk, target_size, 0, tokenizer)
 }
 
-/// Split large chunks into smaller pieces
-pub fn split_large_chunk(chunk: &CodeChunk, target_size: usize) -> Vec<CodeChunk> {
+/// Split large chunks into pieces of at most `target_size` tokens, each
+/// starting with up to `overlap` tokens of lines repeated from the piece
+/// before it
+///
+/// Calls are narrowed to each piece's lines; the definitions and references
+/// of the pieces are left empty, as the chunk's identifiers have no lines.
+pub fn split_large_chunk_with_overlap(
+    chunk: &CodeChunk,
+    target_size: usize,
+    overlap: usize,
+    tokenizer: &dyn Tokenizer,
+) -> Vec<CodeChunk> {
     if chunk.tokens <= target_size {
         return vec![chunk.clone()];
     }
 
-    let mut chunks = Vec::new();
     let lines: Vec<&str> = chunk.text.lines().collect();
-
-    // Try to split at logical boundaries
-    let mut current_start = 0;
-    let mut current_size = 0;
-
-    for (i, line) in lines.iter().enumerate() {
-        let line_tokens = estimate_tokens(line);
-
-        if current_size + line_tokens > target_size && current_start < i {
-            // Create chunk up to this point
-            let chunk_text = lines[current_start..i].join("\n");
-
-            chunks.push(CodeChunk {
-                id: Uuid::new_v4().to_string(),
-                text: chunk_text.clone(),
-                start_line: chunk.start_line + current_start,
-                end_line: chunk.start_line + i,
-                tokens: current_size,
+    let line_tokens: Vec<usize> = lines.iter().map(|line| tokenizer.count_tokens(line)).collect();
+
+    // Pieces as (start, first new line, end) line indices, end exclusive
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut new_start = 0;
+    let mut size = 0;
+    for (i, &tokens) in line_tokens.iter().enumerate() {
+        // Every piece gets at least one line of its own
+        if size + tokens > target_size && new_start < i {
+            pieces.push((start, new_start, i));
+            // The overlap and the piece's first line must fit in the target
+            let budget = overlap.min(target_size.saturating_sub(tokens));
+            start = i - overlap_lines(&line_tokens[new_start + 1..i], budget);
+            new_start = i;
+            size = line_tokens[start..i].iter().sum();
+        }
+        size += tokens;
+    }
+    pieces.push((start, new_start, lines.len()));
+
+    pieces
+        .into_iter()
+        .enumerate()
+        .map(|(index, (start, new_start, end))| {
+            let chunk_text = lines[start..end].join("\n");
+            let start_line = chunk.start_line + start;
+            let end_line = chunk.start_line + end - 1;
+
+            let mut part = CodeChunk {
+                id: String::new(),
+                stable_id: String::new(),
+                content_hash: String::new(),
+                tokens: tokenizer.count_tokens(&chunk_text),
+                text: chunk_text,
+                start_line,
+                end_line,
                 language: chunk.language.clone(),
+                kind: chunk.kind,
+                qualified_name: chunk.qualified_name.clone(),
+                documentation: chunk.documentation.clone(),
                 functions: chunk.functions.clone(),
                 classes: chunk.classes.clone(),
                 imports: chunk.imports.clone(),
                 dependencies: chunk.dependencies.clone(),
-            });
+                calls: calls_in_lines(&chunk.calls, start_line, end_line),
+                defines: Vec::new(),
+                references: Vec::new(),
+                is_test: chunk.is_test,
+                test_subjects: chunk.test_subjects.clone(),
+                has_errors: chunk.has_errors,
+                // The first piece keeps any overlap of the chunk itself
+                overlap: match index {
+                    0 => chunk.overlap,
+                    _ => overlap_range(start_line, chunk.start_line + new_start),
+                },
+            };
+            assign_part_ids(&mut part, chunk, index);
+            part
+        })
+        .collect()
+}
 
-            current_start = i;
-            current_size = line_tokens;
-        } else {
-            current_size += line_tokens;
+/// Repeat the last lines of each line-split piece of top-level code at the
+/// start of the next one
+///
+/// Pieces are adjacent only when they were split from the same section.
+fn overlap_sections(chunks: &mut [CodeChunk], source_lines: &[&str], overlap: usize, tokenizer: &dyn Tokenizer) {
+    for i in 1..chunks.len() {
+        let previous = &chunks[i - 1];
+        if previous.end_line + 1 != chunks[i].start_line {
+            continue;
         }
-    }
 
-    // Add final chunk
-    if current_start < lines.len() {
-        let chunk_text = lines[current_start..].join("\n");
-
-        chunks.push(CodeChunk {
-            id: Uuid::new_v4().to_string(),
-            text: chunk_text,
-            start_line: chunk.start_line + current_start,
-            end_line: chunk.end_line,
-            tokens: current_size,
-            language: chunk.language.clone(),
-            functions: chunk.functions.clone(),
-            classes: chunk.classes.clone(),
-            imports: chunk.imports.clone(),
-            dependencies: chunk.dependencies.clone(),
-        });
+        // The first line of the previous piece's own code is never repeated
+        let first_new_line = previous.overlap.map_or(previous.start_line, |range| range.end_line + 1);
+        let line_tokens: Vec<usize> = source_lines[first_new_line..previous.end_line]
+            .iter()
+            .map(|line| tokenizer.count_tokens(line))
+            .collect();
+        let repeated = overlap_lines(&line_tokens, overlap);
+        if repeated == 0 {
+            continue;
+        }
+
+        let chunk = &mut chunks[i];
+        let start_line = chunk.start_line - repeated;
+        chunk.text = source_lines[start_line - 1..chunk.end_line].join("\n");
+        chunk.overlap = overlap_range(start_line, chunk.start_line);
+        chunk.start_line = start_line;
     }
+}
 
-    chunks
+/// Number of trailing lines that fit in `overlap` tokens, given their counts
+fn overlap_lines(line_tokens: &[usize], overlap: usize) -> usize {
+    let mut size = 0;
+    line_tokens
+        .iter()
+        .rev()
+        .take_while(|&&tokens| {
+            size += tokens;
+            size <= overlap
+        })
+        .count()
+}
+
+/// The lines from `start_line` up to, not including, `new_start_line`
+fn overlap_range(start_line: usize, new_start_line: usize) -> Option<LineRange> {
+    (start_line < new_start_line).then(|| LineRange {
+        start_line,
+        end_line: new_start_line - 1,
+    })
 }
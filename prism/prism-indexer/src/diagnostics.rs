@@ -0,0 +1,197 @@
+//! Syntax error diagnostics
+//!
+//! Turns the ERROR and MISSING nodes of a tree into messages that say what
+//! went wrong and where: the construct being parsed, the token the parser
+//! had to insert, and the tokens the grammar would have accepted instead.
+
+use crate::types::{ErrorNode, Severity, SourceLocation};
+use tree_sitter::Node;
+
+/// Diagnostics reported per file; the rest are usually follow-on errors
+pub const MAX_DIAGNOSTICS: usize = 20;
+
+/// Longest snippet of source quoted in a diagnostic, in characters
+const MAX_SNIPPET_CHARS: usize = 60;
+
+/// Expected-token lists longer than this are too broad to be a useful hint
+const MAX_EXPECTED_TOKENS: usize = 8;
+
+/// Tokens allowed anywhere, which never help as a hint
+const EXTRA_TOKENS: &[&str] = &["comment", "line_comment", "block_comment", "html_comment", "line_continuation"];
+
+/// Missing tokens the parser recovers from without losing structure
+const RECOVERABLE_TOKENS: &[&str] = &[";", ","];
+
+/// Collect the syntax errors of a tree, outermost first
+///
+/// Errors nested inside an ERROR node are part of it and are not reported
+/// again; at most [`MAX_DIAGNOSTICS`] are returned.
+pub fn find_error_nodes(node: &Node, source: &str) -> Vec<ErrorNode> {
+    let mut errors = Vec::new();
+    collect_errors(node, source, &mut errors);
+    errors
+}
+
+fn collect_errors(node: &Node, source: &str, errors: &mut Vec<ErrorNode>) {
+    if errors.len() >= MAX_DIAGNOSTICS || !node.has_error() {
+        return;
+    }
+
+    if node.is_missing() {
+        errors.push(missing_diagnostic(node));
+        return;
+    }
+    if node.is_error() {
+        errors.push(error_diagnostic(node, source));
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_errors(&child, source, errors);
+    }
+}
+
+/// A token or node the parser inserted to recover: `Missing ";" in expression statement`
+fn missing_diagnostic(node: &Node) -> ErrorNode {
+    let kind = node.kind();
+    let missing = if node.is_named() { kind.to_string() } else { format!("\"{}\"", kind) };
+    let parent = construct(node);
+
+    ErrorNode {
+        message: format!("Missing {} {}", missing, describe_construct(parent.as_deref())),
+        location: location(node),
+        text: String::new(),
+        severity: if RECOVERABLE_TOKENS.contains(&kind) { Severity::Warning } else { Severity::Error },
+        parent_kind: parent,
+        missing: Some(kind.to_string()),
+        expected: Vec::new(),
+    }
+}
+
+/// Source the parser could not fit into the grammar: `Unexpected "=>" in arguments`
+fn error_diagnostic(node: &Node, source: &str) -> ErrorNode {
+    let parent = construct(node);
+    let expected = expected_tokens(node);
+
+    let unexpected = first_token(node, source)
+        .map_or_else(|| "input".to_string(), |token| format!("\"{}\"", snippet(&token)));
+    let mut message = format!("Unexpected {} {}", unexpected, describe_construct(parent.as_deref()));
+    if !expected.is_empty() {
+        message.push_str(&format!("; expected {}", expected.join(", ")));
+    }
+
+    ErrorNode {
+        message,
+        location: location(node),
+        text: snippet(&source[node.byte_range()]),
+        severity: Severity::Error,
+        parent_kind: parent,
+        missing: None,
+        expected,
+    }
+}
+
+/// Kind of the nearest well-formed construct around a node
+fn construct(node: &Node) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if !ancestor.is_error() {
+            return Some(ancestor.kind().to_string());
+        }
+        current = ancestor.parent();
+    }
+    None
+}
+
+/// `in function declaration`, or `at top level` for the root node
+fn describe_construct(kind: Option<&str>) -> String {
+    match kind {
+        Some("program" | "source_file" | "module" | "translation_unit") | None => "at top level".to_string(),
+        Some(kind) => format!("in {}", kind.replace('_', " ")),
+    }
+}
+
+/// Tokens the grammar accepts where the error starts, from the parse state
+/// after the last well-formed token before it
+fn expected_tokens(node: &Node) -> Vec<String> {
+    let Some(previous) = previous_leaf(node) else {
+        return Vec::new();
+    };
+    let language = node.language();
+    let Some(lookahead) = language.lookahead_iterator(previous.next_parse_state()) else {
+        return Vec::new();
+    };
+
+    let mut expected: Vec<String> = Vec::new();
+    for symbol in lookahead {
+        // Symbol 0 is the end of input
+        if symbol == 0 || !language.node_kind_is_visible(symbol) {
+            continue;
+        }
+        let Some(name) = language.node_kind_for_id(symbol).filter(|name| !EXTRA_TOKENS.contains(name)) else {
+            continue;
+        };
+        let name = if language.node_kind_is_named(symbol) { name.to_string() } else { format!("\"{}\"", name) };
+        if !expected.contains(&name) {
+            expected.push(name);
+        }
+        if expected.len() > MAX_EXPECTED_TOKENS {
+            return Vec::new();
+        }
+    }
+    expected
+}
+
+/// The last token before a node, skipping comments
+fn previous_leaf<'tree>(node: &Node<'tree>) -> Option<Node<'tree>> {
+    let mut current = *node;
+    loop {
+        match current.prev_sibling() {
+            Some(sibling) if sibling.is_extra() => current = sibling,
+            Some(sibling) => return Some(last_leaf(sibling)),
+            None => current = current.parent()?,
+        }
+    }
+}
+
+fn last_leaf(node: Node) -> Node {
+    let mut node = node;
+    while let Some(child) = node.child(node.child_count().saturating_sub(1)) {
+        node = child;
+    }
+    node
+}
+
+/// Text of the first token inside an error node
+fn first_token(node: &Node, source: &str) -> Option<String> {
+    let mut leaf = *node;
+    while let Some(child) = leaf.child(0) {
+        leaf = child;
+    }
+    let text = source[leaf.byte_range()].trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// First line of some source, shortened to [`MAX_SNIPPET_CHARS`]
+fn snippet(text: &str) -> String {
+    let line = text.trim().lines().next().unwrap_or("");
+    let is_multiline = text.trim().contains('\n');
+    if line.chars().count() > MAX_SNIPPET_CHARS {
+        let shortened: String = line.chars().take(MAX_SNIPPET_CHARS).collect();
+        format!("{}…", shortened.trim_end())
+    } else if is_multiline {
+        format!("{}…", line)
+    } else {
+        line.to_string()
+    }
+}
+
+fn location(node: &Node) -> SourceLocation {
+    SourceLocation {
+        start_row: node.start_position().row,
+        start_column: node.start_position().column,
+        end_row: node.end_position().row,
+        end_column: node.end_position().column,
+    }
+}
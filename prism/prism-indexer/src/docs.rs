@@ -0,0 +1,333 @@
+//! Documentation comment extraction
+//!
+//! Doc text comes from the comments directly above a declaration (JSDoc,
+//! Javadoc, rustdoc, Go and `#` comments) or from a Python docstring.
+//! JSDoc/Javadoc block tags and Sphinx fields are parsed into parameters,
+//! return value and thrown errors.
+
+use crate::types::{DocParam, Documentation};
+use tree_sitter::Node;
+
+/// Comment node kinds across grammars
+const COMMENT_NODES: &[&str] = &["comment", "line_comment", "block_comment"];
+
+/// Siblings that may sit between a doc comment and its declaration
+const SKIPPED_SIBLINGS: &[&str] = &[
+    "decorator",
+    "attribute_item",
+    "attribute_declaration",
+    "annotation",
+    "marker_annotation",
+];
+
+/// Nodes whose leading comments document the declaration they wrap
+const DOC_WRAPPERS: &[&str] = &[
+    "export_statement",
+    "decorated_definition",
+    "template_declaration",
+    "ambient_declaration",
+    "type_declaration",
+    "type_definition",
+    "lexical_declaration",
+    "variable_declaration",
+    "variable_declarator",
+    "expression_statement",
+    "assignment_expression",
+    "block",
+];
+
+/// Extract the documentation of a declaration
+///
+/// `node` is the function or class node itself (which holds a Python
+/// docstring); `anchor` is the node the doc comment precedes, e.g. the
+/// `variable_declarator` of an arrow function.
+pub(crate) fn extract_documentation(node: &Node, anchor: &Node, source: &str) -> Option<Documentation> {
+    let (start_line, raw) = match docstring(node, source) {
+        Some(docstring) => docstring,
+        None => leading_comments(anchor, source)?,
+    };
+
+    let documentation = parse_doc_text(&raw, start_line);
+    let is_empty = documentation.text.is_empty()
+        && documentation.params.is_empty()
+        && documentation.returns.is_none()
+        && documentation.throws.is_empty();
+    (!is_empty).then_some(documentation)
+}
+
+/// Python docstring: a string literal as the first statement of the body
+fn docstring(node: &Node, source: &str) -> Option<(usize, String)> {
+    let body = node.child_by_field_name("body").filter(|b| b.kind() == "block")?;
+    let statement = body.named_child(0).filter(|s| s.kind() == "expression_statement")?;
+    let string = statement.named_child(0).filter(|s| s.kind() == "string")?;
+
+    let literal = &source[string.byte_range()];
+    let literal = literal.trim_start_matches(|c: char| "rRuUbBfF".contains(c));
+    let quote = ["\"\"\"", "'''", "\"", "'"].into_iter().find(|q| literal.starts_with(q))?;
+    let content = literal
+        .strip_prefix(quote)
+        .and_then(|s| s.strip_suffix(quote))
+        .unwrap_or(literal);
+
+    Some((string.start_position().row + 1, clean_docstring(content)))
+}
+
+/// Dedent a docstring the way `inspect.cleandoc` does
+fn clean_docstring(content: &str) -> String {
+    let mut lines = content.lines();
+    let first = lines.next().unwrap_or("").trim().to_string();
+    let rest: Vec<&str> = lines.collect();
+
+    let indent = rest
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    let mut cleaned = vec![first];
+    cleaned.extend(rest.iter().map(|line| line.get(indent..).unwrap_or("").trim_end().to_string()));
+    cleaned.join("\n").trim().to_string()
+}
+
+/// The contiguous run of comments directly above a declaration
+fn leading_comments(anchor: &Node, source: &str) -> Option<(usize, String)> {
+    let mut anchor = *anchor;
+    loop {
+        let (comments, reached_start) = preceding_comments(&anchor);
+        if let Some(first) = comments.last() {
+            let text = comments
+                .iter()
+                .rev()
+                .map(|comment| clean_comment(&source[comment.byte_range()]))
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Some((first.start_position().row + 1, text));
+        }
+
+        // Nothing above the node itself: look above its wrapper
+        // (`export`, decorators, `const x = ...`, Go `type`)
+        match anchor.parent() {
+            Some(parent) if reached_start && DOC_WRAPPERS.contains(&parent.kind()) => anchor = parent,
+            _ => return None,
+        }
+    }
+}
+
+/// Comments directly above `node`, nearest first, and whether the walk
+/// reached the first child of the parent
+fn preceding_comments<'tree>(node: &Node<'tree>) -> (Vec<Node<'tree>>, bool) {
+    let mut comments = Vec::new();
+    let mut next_row = node.start_position().row;
+    let mut current = node.prev_named_sibling();
+
+    while let Some(sibling) = current {
+        if SKIPPED_SIBLINGS.contains(&sibling.kind()) {
+            next_row = sibling.start_position().row;
+            current = sibling.prev_named_sibling();
+            continue;
+        }
+
+        if !COMMENT_NODES.contains(&sibling.kind()) {
+            return (comments, false);
+        }
+
+        // A blank line, an inner doc comment (`//!`) or a trailing comment
+        // after code ends the doc block
+        let is_trailing = sibling
+            .prev_sibling()
+            .is_some_and(|prev| last_row(&prev) == sibling.start_position().row);
+        if last_row(&sibling) + 1 < next_row || is_inner_doc(&sibling) || is_trailing {
+            return (comments, false);
+        }
+
+        comments.push(sibling);
+        next_row = sibling.start_position().row;
+        current = sibling.prev_named_sibling();
+    }
+
+    (comments, true)
+}
+
+/// Last row a node occupies (Rust line comments include their newline)
+fn last_row(node: &Node) -> usize {
+    let end = node.end_position();
+    if end.column == 0 && end.row > node.start_position().row {
+        end.row - 1
+    } else {
+        end.row
+    }
+}
+
+/// Rust inner doc comments document the enclosing module instead
+fn is_inner_doc(comment: &Node) -> bool {
+    let mut cursor = comment.walk();
+    let inner = comment.children(&mut cursor).any(|c| c.kind() == "inner_doc_comment_marker");
+    inner
+}
+
+/// Strip comment markers from a single comment
+fn clean_comment(comment: &str) -> String {
+    let comment = comment.trim();
+
+    if let Some(block) = comment.strip_prefix("/*") {
+        let block = block.strip_suffix("*/").unwrap_or(block);
+        let block = block.trim_start_matches('*');
+        let lines: Vec<String> = block
+            .lines()
+            .map(|line| {
+                let line = line.trim_start();
+                let line = line.strip_prefix('*').unwrap_or(line);
+                line.strip_prefix(' ').unwrap_or(line).trim_end().to_string()
+            })
+            .collect();
+        return lines.join("\n").trim().to_string();
+    }
+
+    let line = comment
+        .strip_prefix("///")
+        .or_else(|| comment.strip_prefix("//"))
+        .or_else(|| comment.strip_prefix('#'))
+        .unwrap_or(comment);
+    line.strip_prefix(' ').unwrap_or(line).trim_end().to_string()
+}
+
+/// Split doc text into its description and block tags
+fn parse_doc_text(raw: &str, start_line: usize) -> Documentation {
+    let mut description = Vec::new();
+    let mut tags: Vec<String> = Vec::new();
+
+    for line in raw.lines() {
+        let trimmed = line.trim();
+        let starts_tag = trimmed.starts_with('@')
+            || [":param", ":arg", ":type", ":return", ":rtype", ":raise", ":except"]
+                .iter()
+                .any(|field| trimmed.starts_with(field));
+
+        if starts_tag {
+            tags.push(trimmed.to_string());
+        } else if let Some(tag) = tags.last_mut() {
+            // Continuation of a multi-line tag
+            if !trimmed.is_empty() {
+                tag.push(' ');
+                tag.push_str(trimmed);
+            }
+        } else {
+            description.push(line);
+        }
+    }
+
+    let mut documentation = Documentation {
+        start_line,
+        text: description.join("\n").trim().to_string(),
+        params: Vec::new(),
+        returns: None,
+        throws: Vec::new(),
+    };
+
+    for tag in &tags {
+        if tag.starts_with(':') {
+            apply_field(tag, &mut documentation);
+        } else {
+            apply_tag(tag, &mut documentation);
+        }
+    }
+
+    documentation
+}
+
+/// JSDoc/Javadoc tags: `@param {T} name - text`, `@returns`, `@throws`
+fn apply_tag(tag: &str, documentation: &mut Documentation) {
+    let (name, rest) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+    let (type_annotation, rest) = braced_type(rest.trim());
+
+    match name {
+        "@param" | "@arg" | "@argument" => {
+            let (param, description) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            // `[name=default]` marks an optional parameter
+            let param = param.trim_start_matches('[').trim_end_matches(']');
+            let param = param.split('=').next().unwrap_or(param);
+            if !param.is_empty() {
+                documentation.params.push(DocParam {
+                    name: param.to_string(),
+                    type_annotation,
+                    description: strip_dash(description),
+                });
+            }
+        }
+        "@returns" | "@return" => {
+            let description = strip_dash(rest);
+            documentation.returns = if description.is_empty() { type_annotation } else { Some(description) };
+        }
+        "@throws" | "@exception" | "@raises" => {
+            let thrown = match type_annotation {
+                Some(type_name) if rest.is_empty() => type_name,
+                Some(type_name) => format!("{} {}", type_name, rest),
+                None => rest.to_string(),
+            };
+            if !thrown.is_empty() {
+                documentation.throws.push(thrown);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Sphinx fields: `:param name: text`, `:returns: text`, `:raises E: text`
+fn apply_field(field: &str, documentation: &mut Documentation) {
+    let Some((head, description)) = field[1..].split_once(':') else {
+        return;
+    };
+    let description = description.trim().to_string();
+    let mut words = head.split_whitespace();
+    let kind = words.next().unwrap_or("");
+    let args: Vec<&str> = words.collect();
+
+    match kind {
+        "param" | "arg" | "parameter" | "argument" => {
+            // `:param int count:` puts the type before the name
+            if let Some((name, type_words)) = args.split_last() {
+                documentation.params.push(DocParam {
+                    name: name.to_string(),
+                    type_annotation: (!type_words.is_empty()).then(|| type_words.join(" ")),
+                    description,
+                });
+            }
+        }
+        "returns" | "return" => documentation.returns = Some(description),
+        "raises" | "raise" | "except" | "exception" => {
+            let thrown = [args.join(" "), description].join(" ").trim().to_string();
+            documentation.throws.push(thrown);
+        }
+        _ => {}
+    }
+}
+
+/// Split a leading `{Type}` off a tag body
+fn braced_type(text: &str) -> (Option<String>, &str) {
+    if !text.starts_with('{') {
+        return (None, text);
+    }
+
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return (Some(text[1..i].trim().to_string()), text[i + 1..].trim());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    (None, text)
+}
+
+/// Drop the `-` separating a JSDoc name from its description
+fn strip_dash(text: &str) -> String {
+    let text = text.trim();
+    text.strip_prefix('-').unwrap_or(text).trim().to_string()
+}
@@ -8,6 +8,15 @@ pub enum PrismError {
     #[error("Parse error: {0}")]
     ParseError(String),
 
+    #[error("Invalid chunking options: {0}")]
+    InvalidOptions(String),
+
+    #[error("Unsupported tokenizer: {0}")]
+    UnsupportedTokenizer(String),
+
+    #[error("Tokenizer error: {0}")]
+    TokenizerError(String),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
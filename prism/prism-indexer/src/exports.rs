@@ -0,0 +1,268 @@
+//! Export extraction
+//!
+//! Lists the names a module makes available to importers: ES module
+//! `export` statements and re-exports, CommonJS assignments, Rust `pub use`
+//! and Python `__all__`. Each export is one `ExportInfo`, the mirror image
+//! of an `ImportBinding`.
+
+use crate::extractor::node_text;
+use crate::imports::{child_of_kind, flatten_use_tree, has_type_keyword, string_value};
+use crate::types::{BindingKind, ExportInfo, ImportBinding, ImportInfo, SourceLocation};
+use tree_sitter::Node;
+
+/// Extract the exports declared at the top level of a file, given its imports
+pub fn extract_exports(root: &Node, source: &str, language: &str, imports: &[ImportInfo]) -> Vec<ExportInfo> {
+    let mut exports = Vec::new();
+
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        match (language, child.kind()) {
+            ("python", "expression_statement") => python_all(&child, source, imports, &mut exports),
+            ("rust", "use_declaration") => rust_pub_use(&child, source, &mut exports),
+            (_, "export_statement") => js_export(&child, source, &mut exports),
+            (_, "expression_statement") => commonjs_export(&child, source, &mut exports),
+            _ => {}
+        }
+    }
+
+    exports
+}
+
+/// `export function f`, `export default x`, `export { a as b }`,
+/// `export * from 'm'`, `export * as ns from 'm'`, TS `export = x`
+fn js_export(node: &Node, source: &str, exports: &mut Vec<ExportInfo>) {
+    let module = node.child_by_field_name("source").map(|s| string_value(&s, source));
+    let is_type_only = has_type_keyword(node);
+    let is_default = has_token(node, "default") || has_token(node, "=");
+
+    if let Some(declaration) = node.child_by_field_name("declaration") {
+        let declaration_type_only = matches!(
+            declaration.kind(),
+            "interface_declaration" | "type_alias_declaration"
+        );
+        for name in declared_names(&declaration, source) {
+            let (exported, kind) = if is_default {
+                ("default".to_string(), BindingKind::Default)
+            } else {
+                (name.clone(), BindingKind::Named)
+            };
+            exports.push(export_info(node, exported, Some(name), None, kind, declaration_type_only));
+        }
+        return;
+    }
+
+    if let Some(clause) = child_of_kind(node, "export_clause") {
+        let mut cursor = clause.walk();
+        for specifier in clause.named_children(&mut cursor).filter(|n| n.kind() == "export_specifier") {
+            let Some(local) = specifier.child_by_field_name("name").map(|n| string_value(&n, source)) else {
+                continue;
+            };
+            let exported = specifier
+                .child_by_field_name("alias")
+                .map_or_else(|| local.clone(), |n| string_value(&n, source));
+            let kind = if exported == "default" { BindingKind::Default } else { BindingKind::Named };
+            let type_only = is_type_only || has_type_keyword(&specifier);
+            exports.push(export_info(node, exported, Some(local), module.clone(), kind, type_only));
+        }
+        return;
+    }
+
+    if let Some(namespace) = child_of_kind(node, "namespace_export") {
+        let name = child_of_kind(&namespace, "identifier").map(|n| string_value(&n, source)).unwrap_or_default();
+        exports.push(export_info(node, name, None, module, BindingKind::Namespace, is_type_only));
+        return;
+    }
+
+    if module.is_some() {
+        exports.push(export_info(node, "*".to_string(), None, module, BindingKind::Wildcard, is_type_only));
+        return;
+    }
+
+    if is_default {
+        // `export default foo;`, `export = foo;`, `export default class Foo {}`;
+        // anonymous values have no local name
+        let value = node.child_by_field_name("value").or_else(|| child_of_kind(node, "identifier"));
+        let local = value.and_then(|value| match value.kind() {
+            "identifier" => Some(node_text(&value, source)),
+            _ => value.child_by_field_name("name").map(|name| node_text(&name, source)),
+        });
+        exports.push(export_info(node, "default".to_string(), local, None, BindingKind::Default, false));
+    }
+}
+
+/// Names introduced by an exported declaration
+fn declared_names(declaration: &Node, source: &str) -> Vec<String> {
+    match declaration.kind() {
+        "lexical_declaration" | "variable_declaration" => {
+            let mut cursor = declaration.walk();
+            let names = declaration
+                .named_children(&mut cursor)
+                .filter(|n| n.kind() == "variable_declarator")
+                .filter_map(|declarator| declarator.child_by_field_name("name"))
+                .filter(|name| name.kind() == "identifier")
+                .map(|name| node_text(&name, source))
+                .collect();
+            names
+        }
+        // `export declare const x: T`
+        "ambient_declaration" => declaration
+            .named_child(0)
+            .map(|inner| declared_names(&inner, source))
+            .unwrap_or_default(),
+        _ => declaration
+            .child_by_field_name("name")
+            .map(|name| vec![node_text(&name, source)])
+            .unwrap_or_default(),
+    }
+}
+
+/// `module.exports = x`, `module.exports = { a, b: c }`,
+/// `module.exports = require('m')`, `exports.a = x`
+fn commonjs_export(node: &Node, source: &str, exports: &mut Vec<ExportInfo>) {
+    let Some(assignment) = node.named_child(0).filter(|n| n.kind() == "assignment_expression") else {
+        return;
+    };
+    let (Some(left), Some(right)) = (
+        assignment.child_by_field_name("left"),
+        assignment.child_by_field_name("right"),
+    ) else {
+        return;
+    };
+
+    let target = node_text(&left, source);
+    let local = (right.kind() == "identifier").then(|| node_text(&right, source));
+
+    if target == "module.exports" {
+        match right.kind() {
+            "object" => {
+                let mut cursor = right.walk();
+                for property in right.named_children(&mut cursor) {
+                    let (name, local) = match property.kind() {
+                        "shorthand_property_identifier" => {
+                            let name = node_text(&property, source);
+                            (name.clone(), Some(name))
+                        }
+                        "pair" => {
+                            let Some(key) = property.child_by_field_name("key") else {
+                                continue;
+                            };
+                            let local = property
+                                .child_by_field_name("value")
+                                .filter(|v| v.kind() == "identifier")
+                                .map(|v| node_text(&v, source));
+                            (string_value(&key, source), local)
+                        }
+                        _ => continue,
+                    };
+                    exports.push(export_info(node, name, local, None, BindingKind::Named, false));
+                }
+            }
+            "call_expression" if required_module(&right, source).is_some() => {
+                let module = required_module(&right, source);
+                exports.push(export_info(node, "*".to_string(), None, module, BindingKind::Wildcard, false));
+            }
+            _ => {
+                let local = local.or_else(|| right.child_by_field_name("name").map(|n| node_text(&n, source)));
+                exports.push(export_info(node, "default".to_string(), local, None, BindingKind::Default, false));
+            }
+        }
+    } else if target.starts_with("module.exports.") || target.starts_with("exports.") {
+        if let Some(property) = left.child_by_field_name("property") {
+            let name = node_text(&property, source);
+            exports.push(export_info(node, name, local, None, BindingKind::Named, false));
+        }
+    }
+}
+
+/// Module of a `require('m')` call
+fn required_module(call: &Node, source: &str) -> Option<String> {
+    let function = call.child_by_field_name("function")?;
+    if node_text(&function, source) != "require" {
+        return None;
+    }
+    let argument = call.child_by_field_name("arguments")?.named_child(0)?;
+    (argument.kind() == "string").then(|| string_value(&argument, source))
+}
+
+/// `pub use a::{b as c, d::*};`: restricted `pub(crate)` uses stay internal
+fn rust_pub_use(node: &Node, source: &str, exports: &mut Vec<ExportInfo>) {
+    let is_public = child_of_kind(node, "visibility_modifier").is_some_and(|v| node_text(&v, source) == "pub");
+    let Some(argument) = node.child_by_field_name("argument").filter(|_| is_public) else {
+        return;
+    };
+
+    let mut leaves: Vec<(Vec<String>, ImportBinding)> = Vec::new();
+    flatten_use_tree(&argument, &[], source, &mut leaves);
+
+    for (path, leaf) in leaves {
+        let module = Some(path.join("::"));
+        let info = match leaf.kind {
+            BindingKind::Wildcard => export_info(node, "*".to_string(), None, module, leaf.kind, false),
+            BindingKind::Namespace => export_info(node, leaf.local, None, module, leaf.kind, false),
+            _ => export_info(node, leaf.local, Some(leaf.imported), module, leaf.kind, false),
+        };
+        exports.push(info);
+    }
+}
+
+/// `__all__ = ["a", "b"]` and `__all__ += [...]`; names imported from
+/// another module are recorded as re-exports of it
+fn python_all(node: &Node, source: &str, imports: &[ImportInfo], exports: &mut Vec<ExportInfo>) {
+    let Some(assignment) = node
+        .named_child(0)
+        .filter(|n| n.kind() == "assignment" || n.kind() == "augmented_assignment")
+    else {
+        return;
+    };
+    let is_all = assignment
+        .child_by_field_name("left")
+        .is_some_and(|left| node_text(&left, source) == "__all__");
+    let Some(right) = assignment.child_by_field_name("right").filter(|_| is_all) else {
+        return;
+    };
+
+    let mut cursor = right.walk();
+    for item in right.named_children(&mut cursor).filter(|n| n.kind() == "string") {
+        let name = string_value(&item, source);
+        let origin = imports.iter().find_map(|import| {
+            let binding = import.bindings.iter().find(|b| b.local == name)?;
+            Some((binding.imported.clone(), import.source.clone()))
+        });
+        let info = match origin {
+            Some((imported, module)) => export_info(node, name, Some(imported), Some(module), BindingKind::Named, false),
+            None => export_info(node, name.clone(), Some(name), None, BindingKind::Named, false),
+        };
+        exports.push(info);
+    }
+}
+
+/// Build an `ExportInfo` located at the exporting statement
+fn export_info(
+    node: &Node,
+    name: String,
+    local: Option<String>,
+    source: Option<String>,
+    kind: BindingKind,
+    is_type_only: bool,
+) -> ExportInfo {
+    ExportInfo {
+        name,
+        local,
+        source,
+        kind,
+        is_type_only,
+        location: SourceLocation {
+            start_row: node.start_position().row,
+            start_column: node.start_position().column,
+            end_row: node.end_position().row,
+            end_column: node.end_position().column,
+        },
+    }
+}
+
+/// Whether a node has an anonymous token of the given kind
+fn has_token(node: &Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).any(|n| !n.is_named() && n.kind() == kind);
+    found
+}
@@ -1,200 +1,846 @@
-use crate::types::{FunctionInfo, ClassInfo, ImportInfo, ErrorNode, SourceLocation};
+use crate::calls::{extract_calls, function_calls};
+use crate::docs::extract_documentation;
+use crate::identifiers::{extract_identifiers, Occurrence};
+use crate::imports::extract_imports;
+use crate::metrics::function_metrics;
+use crate::symbols::{extract_symbols, symbol_kind, symbol_scope};
+use crate::testing::{is_test_function, link_tests, TestIndex};
+use crate::types::{CallSite, FunctionInfo, ClassInfo, ImportInfo, Parameter, SymbolInfo, SymbolKind};
+use crate::visibility::{resolve_visibility, ModuleExports};
+use std::collections::HashMap;
 use tree_sitter::Node;
 
-/// Extract all imports from the AST
-pub fn extract_imports(root: &Node, source: &str) -> Vec<ImportInfo> {
-    let mut imports = Vec::new();
+/// Per-file state shared by the extraction passes
+pub(crate) struct FileContext<'a> {
+    pub(crate) source: &'a str,
+    pub(crate) language: &'a str,
+    pub(crate) exports: ModuleExports,
+    /// Rust `impl Trait for Type` blocks, keyed by type name
+    pub(crate) trait_impls: HashMap<String, Vec<String>>,
+}
 
-    // Walk the tree and find import statements
-    let mut cursor = root.walk();
-    for child in root.children(&mut cursor) {
-        match child.kind() {
-            "import_statement" | "import_declaration" | "import_alias" |
-            "export_statement" | "export_declaration" | "use_declaration" |
-            "use_list" | "call_expression" => {
-                if let Some(imp) = extract_import_info(&child, source) {
-                    imports.push(imp);
-                }
-            }
-            _ => {
-                // Recurse into child nodes
-                imports.extend(extract_imports(&child, source));
-            }
+impl<'a> FileContext<'a> {
+    pub(crate) fn new(root: &Node, source: &'a str, language: &'a str) -> Self {
+        let mut trait_impls = HashMap::new();
+        if language == "rust" {
+            collect_trait_impls(root, source, &mut trait_impls);
+        }
+
+        FileContext {
+            source,
+            language,
+            exports: ModuleExports::collect(root, source, language),
+            trait_impls,
         }
     }
+}
+
+/// Everything chunking and the parse result need from a file's syntax
+/// tree, so that each pass over the tree runs once
+pub(crate) struct FileSyntax {
+    pub(crate) functions: Vec<FunctionInfo>,
+    pub(crate) classes: Vec<ClassInfo>,
+    pub(crate) symbols: Vec<SymbolInfo>,
+    pub(crate) imports: Vec<ImportInfo>,
+    pub(crate) calls: Vec<CallSite>,
+    pub(crate) identifiers: Vec<Occurrence>,
+    /// `None` when the file has no tests
+    pub(crate) tests: Option<TestIndex>,
+}
+
+impl FileSyntax {
+    pub(crate) fn extract(root: &Node, source: &str, language: &str) -> Self {
+        let ctx = FileContext::new(root, source, language);
+        let imports = extract_imports(root, source, language);
+        let symbols = extract_symbols(root, &ctx);
+        let calls = extract_calls(root, source);
+        let identifiers = extract_identifiers(root, source);
+        let tests = TestIndex::new(root, source, language, &imports, &symbols);
 
-    imports
+        let mut functions = extract_functions(root, &ctx);
+        if let Some(tests) = &tests {
+            link_tests(tests, &calls, &identifiers, &mut functions);
+        }
+
+        FileSyntax {
+            functions,
+            classes: extract_classes(root, &ctx),
+            symbols,
+            imports,
+            calls,
+            identifiers,
+            tests,
+        }
+    }
 }
 
-/// Extract all functions from the AST
-pub fn extract_functions(root: &Node, source: &str) -> Vec<FunctionInfo> {
+/// Extract all functions from the AST; test subjects are linked by
+/// [`FileSyntax::extract`]
+pub fn extract_functions(root: &Node, ctx: &FileContext) -> Vec<FunctionInfo> {
     let mut functions = Vec::new();
+    collect_functions(root, ctx, &mut functions);
+    functions
+}
 
-    // Walk the tree and find function definitions
-    let mut cursor = root.walk();
-    for child in root.children(&mut cursor) {
+/// Walk the tree and find function definitions
+fn collect_functions(node: &Node, ctx: &FileContext, functions: &mut Vec<FunctionInfo>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
         match child.kind() {
             "function_declaration" | "function_definition" | "method_definition" |
-            "arrow_function" | "function_expression" => {
-                if let Some(func) = extract_function_info(&child, source) {
+            "arrow_function" | "function_expression" | "function_item" |
+            "generator_function_declaration" | "generator_function" |
+            "method_declaration" | "constructor_declaration" => {
+                if let Some(func) = extract_function_info(&child, ctx) {
                     functions.push(func);
                 }
             }
-            _ => {
-                // Recurse into child nodes
-                functions.extend(extract_functions(&child, source));
-            }
+            _ => {}
         }
-    }
 
-    functions
+        // Keep going: nested functions, closures and local classes' methods
+        collect_functions(&child, ctx, functions);
+    }
 }
 
 /// Extract all classes from the AST
-pub fn extract_classes(root: &Node, source: &str) -> Vec<ClassInfo> {
+pub fn extract_classes(root: &Node, ctx: &FileContext) -> Vec<ClassInfo> {
     let mut classes = Vec::new();
+    collect_classes(root, ctx, &mut classes);
+    classes
+}
 
-    let mut cursor = root.walk();
-    for child in root.children(&mut cursor) {
+/// Walk the tree and find class definitions
+fn collect_classes(node: &Node, ctx: &FileContext, classes: &mut Vec<ClassInfo>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
         if child.kind() == "class_declaration" || child.kind() == "class_definition" ||
-           child.kind() == "interface_declaration" || child.kind() == "type_declaration" {
-            if let Some(class) = extract_class_info(&child, source) {
+           child.kind() == "abstract_class_declaration" || child.kind() == "interface_declaration" ||
+           child.kind() == "enum_declaration" || child.kind() == "record_declaration" ||
+           child.kind() == "class_specifier" || child.kind() == "struct_specifier" ||
+           child.kind() == "union_specifier" || child.kind() == "enum_specifier" ||
+           child.kind() == "struct_item" || child.kind() == "enum_item" ||
+           child.kind() == "trait_item" {
+            if let Some(class) = extract_class_info(&child, ctx) {
                 classes.push(class);
             }
+            // Inner classes
+            collect_classes(&child, ctx, classes);
+        } else if child.kind() == "type_declaration" {
+            // Go groups type specs: `type ( A struct{...}; B interface{...} )`
+            let mut spec_cursor = child.walk();
+            for spec in child.named_children(&mut spec_cursor).filter(|n| n.kind() == "type_spec") {
+                if let Some(class) = extract_class_info(&spec, ctx) {
+                    classes.push(class);
+                }
+            }
         } else {
-            classes.extend(extract_classes(&child, source));
+            collect_classes(&child, ctx, classes);
         }
     }
-
-    classes
 }
 
 /// Extract information from a function node
-fn extract_function_info(node: &Node, source: &str) -> Option<FunctionInfo> {
-    let name_node = node.child_by_field_name("name")?;
-    let name = name_node.utf8_text(source.as_bytes()).ok()?.to_string();
+fn extract_function_info(node: &Node, ctx: &FileContext) -> Option<FunctionInfo> {
+    let source = ctx.source;
+    let name = function_name(node, source)?;
 
-    let start_line = node.start_position().row + 1;
+    // Function values are described from the declaration that binds them:
+    // `const handler = async (req) => {...}`
+    let binding = function_binding(node);
+    let declaration = binding
+        .map(|binding| {
+            binding
+                .parent()
+                .filter(|p| p.kind() == "lexical_declaration" || p.kind() == "variable_declaration")
+                .unwrap_or(binding)
+        })
+        .unwrap_or(*node);
+
+    let start_line = declaration.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    // Extract signature
-    let signature = source
-        [node.start_byte()..node.child_by_field_name("body")?.start_byte()]
-        .to_string();
+    // Extract signature (C/C++ method declarations have no body)
+    let body = node.child_by_field_name("body");
+    let signature = match body {
+        Some(body) => source[declaration.start_byte()..body.start_byte()].to_string(),
+        None => source[node.byte_range()].trim_end_matches(';').to_string(),
+    };
+
+    // React-style components: capitalised functions that return JSX
+    let is_component = name.starts_with(|c: char| c.is_ascii_uppercase())
+        && body.is_some_and(|body| returns_jsx(&body));
+
+    let (visibility, is_exported) = resolve_visibility(&binding.unwrap_or(*node), &name, ctx);
+    let (depth, enclosing_symbol) = symbol_scope(node, ctx);
+    let documentation = extract_documentation(node, &binding.unwrap_or(*node), source);
+    let parameters = extract_parameters(node, source);
+    let metrics = function_metrics(node, &declaration, &parameters, source);
 
     Some(FunctionInfo {
         name,
+        kind: symbol_kind(node, ctx).unwrap_or(SymbolKind::Function),
         signature,
         start_line,
         end_line,
-        parameters: Vec::new(),
-        return_type: None,
-        is_async: node.child_by_field_name("async").is_some(),
-        is_exported: false,
+        parameters,
+        return_type: extract_return_type(node, source),
+        is_async: is_async(node),
+        is_exported,
+        visibility,
+        is_component,
+        depth,
+        enclosing_symbol,
+        documentation,
+        calls: function_calls(node, source),
+        is_test: is_test_function(node, source, ctx.language),
+        test_subjects: Vec::new(),
+        metrics,
     })
 }
 
+/// Whether a function is declared `async`
+fn is_async(node: &Node) -> bool {
+    let mut cursor = node.walk();
+    let is_async = node.children(&mut cursor).any(|child| match child.kind() {
+        "async" => true,
+        // Rust: `pub async fn`
+        "function_modifiers" => {
+            let mut modifier_cursor = child.walk();
+            let found = child.children(&mut modifier_cursor).any(|m| m.kind() == "async");
+            found
+        }
+        _ => false,
+    });
+    is_async
+}
+
+/// Extract the parameter list of a function node
+fn extract_parameters(node: &Node, source: &str) -> Vec<Parameter> {
+    // Single-identifier arrow functions: `x => x * 2`
+    if let Some(param) = node.child_by_field_name("parameter") {
+        return vec![simple_parameter(node_text(&param, source))];
+    }
+
+    // C/C++ keep the parameter list on the function declarator
+    let params_node = match node.child_by_field_name("parameters") {
+        Some(params_node) => params_node,
+        None => match function_declarator(node).and_then(|d| d.child_by_field_name("parameters")) {
+            Some(params_node) => params_node,
+            None => return Vec::new(),
+        },
+    };
+
+    let mut parameters = Vec::new();
+    let mut cursor = params_node.walk();
+    for child in params_node.children(&mut cursor) {
+        if child.is_named() {
+            parameters.extend(extract_parameter(&child, source));
+        } else if child.kind() == "..." {
+            // C/C++ variadic functions: `printf(const char *fmt, ...)`
+            parameters.push(Parameter {
+                is_variadic: true,
+                ..simple_parameter("...".to_string())
+            });
+        }
+    }
+
+    parameters
+}
+
+/// Extract one parameter node (Go can declare several names at once: `a, b int`)
+fn extract_parameter(node: &Node, source: &str) -> Vec<Parameter> {
+    let field_text = |field: &str| node.child_by_field_name(field).map(|n| node_text(&n, source));
+
+    let param = match node.kind() {
+        // JavaScript / Python / Rust `self`
+        "identifier" | "self_parameter" | "object_pattern" | "array_pattern" => {
+            simple_parameter(node_text(node, source))
+        }
+
+        // TypeScript
+        "required_parameter" | "optional_parameter" => {
+            let pattern = node.child_by_field_name("pattern");
+            let is_variadic = pattern.is_some_and(|p| p.kind() == "rest_pattern");
+            let name = pattern
+                .map(|p| node_text(&p, source).trim_start_matches("...").to_string())
+                .unwrap_or_default();
+            let default_value = field_text("value");
+
+            Parameter {
+                name,
+                type_annotation: field_text("type").map(|t| strip_type_colon(&t)),
+                is_optional: node.kind() == "optional_parameter" || default_value.is_some(),
+                default_value,
+                is_variadic,
+            }
+        }
+
+        // JavaScript defaults and rest parameters
+        "assignment_pattern" => Parameter {
+            name: field_text("left").unwrap_or_default(),
+            type_annotation: None,
+            default_value: field_text("right"),
+            is_variadic: false,
+            is_optional: true,
+        },
+        "rest_pattern" => Parameter {
+            is_variadic: true,
+            ..simple_parameter(node_text(node, source).trim_start_matches("...").to_string())
+        },
+
+        // Python
+        "typed_parameter" => {
+            let inner = node.named_child(0);
+            let is_variadic = inner.is_some_and(|n| n.kind().ends_with("splat_pattern"));
+            let name = inner.map(|n| splat_name(&n, source)).unwrap_or_default();
+
+            Parameter {
+                type_annotation: field_text("type"),
+                is_variadic,
+                ..simple_parameter(name)
+            }
+        }
+        "default_parameter" | "typed_default_parameter" => Parameter {
+            name: field_text("name").unwrap_or_default(),
+            type_annotation: field_text("type"),
+            default_value: field_text("value"),
+            is_variadic: false,
+            is_optional: true,
+        },
+        "list_splat_pattern" | "dictionary_splat_pattern" => Parameter {
+            is_variadic: true,
+            ..simple_parameter(splat_name(node, source))
+        },
+
+        // Rust
+        "parameter" => Parameter {
+            type_annotation: field_text("type"),
+            ..simple_parameter(field_text("pattern").unwrap_or_default())
+        },
+        "variadic_parameter" => Parameter {
+            is_variadic: true,
+            ..simple_parameter(field_text("pattern").unwrap_or_else(|| "...".to_string()))
+        },
+
+        // Go
+        "parameter_declaration" if node.child_by_field_name("name").is_some() => {
+            let type_annotation = field_text("type");
+            let mut cursor = node.walk();
+            let names: Vec<String> = node
+                .children_by_field_name("name", &mut cursor)
+                .map(|n| node_text(&n, source))
+                .collect();
+
+            return names
+                .into_iter()
+                .map(|name| Parameter {
+                    type_annotation: type_annotation.clone(),
+                    ..simple_parameter(name)
+                })
+                .collect();
+        }
+        "variadic_parameter_declaration" if node.child_by_field_name("name").is_some() => Parameter {
+            type_annotation: field_text("type"),
+            is_variadic: true,
+            ..simple_parameter(field_text("name").unwrap_or_default())
+        },
+
+        // Java
+        "formal_parameter" => Parameter {
+            type_annotation: field_text("type"),
+            ..simple_parameter(field_text("name").unwrap_or_default())
+        },
+        "spread_parameter" => {
+            let mut cursor = node.walk();
+            let children: Vec<Node> = node.named_children(&mut cursor).collect();
+            let type_annotation = children
+                .iter()
+                .find(|n| n.kind() != "modifiers" && n.kind() != "variable_declarator")
+                .map(|n| node_text(n, source));
+            let name = children
+                .iter()
+                .find(|n| n.kind() == "variable_declarator")
+                .and_then(|n| n.child_by_field_name("name"))
+                .map(|n| node_text(&n, source))
+                .unwrap_or_default();
+
+            Parameter {
+                type_annotation,
+                is_variadic: true,
+                ..simple_parameter(name)
+            }
+        }
+
+        // C/C++, and unnamed Go parameters (`func(int, string)`)
+        "parameter_declaration" | "optional_parameter_declaration" |
+        "variadic_parameter_declaration" => c_parameter(node, source),
+
+        _ => return Vec::new(),
+    };
+
+    vec![param]
+}
+
+/// Extract a C/C++ parameter: the name sits at the bottom of the declarator
+/// chain and the type is everything around it (`const char *msg`)
+fn c_parameter(node: &Node, source: &str) -> Parameter {
+    let declarator = node.child_by_field_name("declarator");
+    let default_value = node
+        .child_by_field_name("default_value")
+        .map(|n| node_text(&n, source));
+
+    // Reference and variadic declarators (`T &x`, `Args... args`) wrap the
+    // identifier without a field
+    let mut name_node = declarator;
+    while let Some(current) = name_node {
+        let inner = match current.kind() {
+            "reference_declarator" | "variadic_declarator" => current.named_child(0),
+            _ => current.child_by_field_name("declarator"),
+        };
+        match inner {
+            Some(inner) => name_node = Some(inner),
+            None => break,
+        }
+    }
+
+    let type_end = declarator.map_or(node.end_byte(), |d| d.end_byte());
+    let type_annotation = match name_node {
+        Some(name) => format!(
+            "{}{}",
+            &source[node.start_byte()..name.start_byte()],
+            &source[name.end_byte()..type_end]
+        ),
+        None => source[node.start_byte()..type_end].to_string(),
+    };
+    let type_annotation = type_annotation.trim().to_string();
+
+    Parameter {
+        name: name_node.map(|n| node_text(&n, source)).unwrap_or_default(),
+        type_annotation: Some(type_annotation).filter(|t| !t.is_empty()),
+        is_optional: default_value.is_some(),
+        default_value,
+        is_variadic: node.kind() == "variadic_parameter_declaration",
+    }
+}
+
+/// Extract the declared return type of a function node
+fn extract_return_type(node: &Node, source: &str) -> Option<String> {
+    // TypeScript/Python/Rust: `return_type`, Go: `result`
+    if let Some(return_type) = node
+        .child_by_field_name("return_type")
+        .or_else(|| node.child_by_field_name("result"))
+    {
+        return Some(strip_type_colon(&node_text(&return_type, source)));
+    }
+
+    // Java and C/C++: `type` precedes the name. C/C++ qualifiers and
+    // pointer/reference declarators (`const char *make()`) belong to it too.
+    let type_node = node.child_by_field_name("type")?;
+    let mut cursor = node.walk();
+    let mut return_type: Vec<String> = node
+        .named_children(&mut cursor)
+        .filter(|n| n.kind() == "type_qualifier" && n.start_byte() < type_node.start_byte())
+        .map(|n| node_text(&n, source))
+        .collect();
+    return_type.push(node_text(&type_node, source));
+    let return_type = return_type.join(" ");
+
+    let mut suffix = String::new();
+    let mut current = node.child_by_field_name("declarator");
+    while let Some(declarator) = current {
+        match declarator.kind() {
+            "pointer_declarator" => suffix.push('*'),
+            "reference_declarator" => suffix.push('&'),
+            _ => break,
+        }
+        current = declarator
+            .child_by_field_name("declarator")
+            .or_else(|| declarator.named_child(0));
+    }
+
+    if suffix.is_empty() {
+        Some(return_type)
+    } else {
+        Some(format!("{} {}", return_type, suffix))
+    }
+}
+
+/// Name of a Python `*args` / `**kwargs` pattern, without the stars
+fn splat_name(node: &Node, source: &str) -> String {
+    node_text(node, source).trim_start_matches('*').to_string()
+}
+
+/// A parameter with only a name
+fn simple_parameter(name: String) -> Parameter {
+    Parameter {
+        name,
+        type_annotation: None,
+        default_value: None,
+        is_variadic: false,
+        is_optional: false,
+    }
+}
+
+/// Strip the leading `:` of a TypeScript type annotation
+fn strip_type_colon(text: &str) -> String {
+    text.trim_start_matches(':').trim().to_string()
+}
+
+/// Get the source text of a node
+pub(crate) fn node_text(node: &Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+/// Check whether a function body returns JSX
+///
+/// Arrow functions may return JSX directly as their expression body; block
+/// bodies are searched for `return` statements.
+fn returns_jsx(body: &Node) -> bool {
+    if body.kind() != "statement_block" {
+        return is_jsx_expression(body);
+    }
+
+    contains_jsx_return(body)
+}
+
+/// Look for a `return` of JSX, without descending into nested functions or classes
+fn contains_jsx_return(node: &Node) -> bool {
+    let mut cursor = node.walk();
+    let found = node.named_children(&mut cursor).any(|child| match child.kind() {
+        "return_statement" => child.named_child(0).is_some_and(|expr| is_jsx_expression(&expr)),
+        "function_declaration" | "function_expression" | "arrow_function" |
+        "method_definition" | "class_declaration" | "class" => false,
+        _ => contains_jsx_return(&child),
+    });
+    found
+}
+
+/// Check whether an expression evaluates to JSX (`<A/>`, `(<A/>)`, `c ? <A/> : null`, `c && <A/>`)
+fn is_jsx_expression(node: &Node) -> bool {
+    match node.kind() {
+        "jsx_element" | "jsx_self_closing_element" | "jsx_fragment" => true,
+        "parenthesized_expression" => node.named_child(0).is_some_and(|expr| is_jsx_expression(&expr)),
+        "ternary_expression" => {
+            ["consequence", "alternative"].iter().any(|field| {
+                node.child_by_field_name(field).is_some_and(|expr| is_jsx_expression(&expr))
+            })
+        }
+        "binary_expression" => node.child_by_field_name("right").is_some_and(|expr| is_jsx_expression(&expr)),
+        _ => false,
+    }
+}
+
 /// Extract information from a class node
-fn extract_class_info(node: &Node, source: &str) -> Option<ClassInfo> {
-    let name_node = node.child_by_field_name("name")?;
-    let name = name_node.utf8_text(source.as_bytes()).ok()?.to_string();
+fn extract_class_info(node: &Node, ctx: &FileContext) -> Option<ClassInfo> {
+    let source = ctx.source;
+    let name = class_name(node, source)?;
 
     let start_line = node.start_position().row + 1;
     let end_line = node.end_position().row + 1;
 
-    // Extract methods
-    let body_node = node.child_by_field_name("body")?;
+    // Extract methods (Go keeps struct and interface bodies in the `type` field);
+    // body-less types such as `struct Marker;` have no members
+    let body_node = node.child_by_field_name("body").or_else(|| {
+        node.child_by_field_name("type")
+            .filter(|t| t.kind() == "struct_type" || t.kind() == "interface_type")
+    });
     let mut methods = Vec::new();
 
-    let mut cursor = body_node.walk();
-    for child in body_node.children(&mut cursor) {
-        if child.kind() == "method_definition" {
-            if let Some(method) = extract_function_info(&child, source) {
+    // Java enums keep their methods after the constants
+    let mut cursor = node.walk();
+    let children: Vec<Node> = body_node.map_or_else(Vec::new, |body| body.children(&mut cursor).collect());
+    let members: Vec<Node> = children
+        .into_iter()
+        .flat_map(|child| {
+            if child.kind() == "enum_body_declarations" {
+                let mut inner_cursor = child.walk();
+                child.children(&mut inner_cursor).collect()
+            } else {
+                vec![child]
+            }
+        })
+        .collect();
+
+    for child in members {
+        let is_method = match child.kind() {
+            "method_definition" | "function_definition" | "method_declaration" |
+            "constructor_declaration" | "function_item" | "function_signature_item" |
+            "method_elem" | "method_spec" | "method_signature" | "abstract_method_signature" => true,
+            // C++ member function declarations: `virtual void draw() const;`
+            "field_declaration" | "declaration" => function_declarator(&child).is_some(),
+            _ => false,
+        };
+
+        // Function-valued class fields: `onClick = () => {...}`
+        let field_function = match child.kind() {
+            "public_field_definition" | "field_definition" => child
+                .child_by_field_name("value")
+                .filter(|value| function_binding(value).is_some()),
+            _ => None,
+        };
+
+        if is_method {
+            if let Some(method) = extract_function_info(&child, ctx) {
+                methods.push(method);
+            }
+        } else if let Some(value) = field_function {
+            if let Some(method) = extract_function_info(&value, ctx) {
                 methods.push(method);
             }
         }
     }
 
+    let (visibility, is_exported) = resolve_visibility(node, &name, ctx);
+    let (extends, implements) = extract_heritage(node, &name, ctx);
+    let (depth, enclosing_symbol) = symbol_scope(node, ctx);
+    let documentation = extract_documentation(node, node, source);
+
     Some(ClassInfo {
         name,
-        extends: None,
-        implements: Vec::new(),
+        kind: symbol_kind(node, ctx).unwrap_or(SymbolKind::Class),
+        extends,
+        implements,
         methods,
         start_line,
         end_line,
+        is_exported,
+        visibility,
+        depth,
+        enclosing_symbol,
+        documentation,
     })
 }
 
-/// Find all error nodes in the tree
-pub fn find_error_nodes(node: &Node, source: &str) -> Vec<ErrorNode> {
-    let mut errors = Vec::new();
+/// Parse the supertypes of a class-like node
+///
+/// `extends` holds the primary base class where the language has one; every
+/// other supertype (interfaces, mixins, super-interfaces, embedded Go types,
+/// implemented Rust traits) goes into `implements`.
+fn extract_heritage(node: &Node, name: &str, ctx: &FileContext) -> (Option<String>, Vec<String>) {
+    let source = ctx.source;
+    let mut extends = None;
+    let mut implements = Vec::new();
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            // TS: `extends B<T> implements C, D`; JS: `extends expr`
+            "class_heritage" => {
+                let mut heritage_cursor = child.walk();
+                for clause in child.named_children(&mut heritage_cursor) {
+                    match clause.kind() {
+                        "extends_clause" => {
+                            let start = clause.child_by_field_name("value").map(|v| v.start_byte());
+                            if let Some(start) = start {
+                                let end = clause
+                                    .child_by_field_name("type_arguments")
+                                    .map_or_else(|| clause.end_byte(), |t| t.end_byte());
+                                extends = Some(source[start..end].to_string());
+                            }
+                        }
+                        "implements_clause" => implements.extend(named_children_text(&clause, source)),
+                        _ => extends = Some(node_text(&clause, source)),
+                    }
+                }
+            }
+            // TS interfaces: `extends J, K<L>`
+            "extends_type_clause" => implements.extend(named_children_text(&child, source)),
+            // Java: `extends B`, `implements C, D`, interface `extends J, K`
+            "superclass" => extends = child.named_child(0).map(|t| node_text(&t, source)),
+            "super_interfaces" | "extends_interfaces" => {
+                if let Some(types) = child.named_child(0) {
+                    implements.extend(named_children_text(&types, source));
+                }
+            }
+            // Python: `class A(Base, Mixin, metaclass=Meta)`
+            "argument_list" => {
+                let mut bases_cursor = child.walk();
+                let bases = child
+                    .named_children(&mut bases_cursor)
+                    .filter(|b| b.kind() != "keyword_argument" && b.kind() != "comment")
+                    .map(|b| node_text(&b, source))
+                    .filter(|b| b != "object");
+                for base in bases {
+                    if extends.is_none() {
+                        extends = Some(base);
+                    } else {
+                        implements.push(base);
+                    }
+                }
+            }
+            // C++: `class A : public B, private C`
+            "base_class_clause" => {
+                let mut bases_cursor = child.walk();
+                let bases = child
+                    .named_children(&mut bases_cursor)
+                    .filter(|b| b.kind() != "access_specifier")
+                    .map(|b| node_text(&b, source));
+                for base in bases {
+                    if extends.is_none() {
+                        extends = Some(base);
+                    } else {
+                        implements.push(base);
+                    }
+                }
+            }
+            // Rust supertraits: `trait A: B + C`
+            "trait_bounds" => implements.extend(named_children_text(&child, source)),
+            _ => {}
+        }
+    }
+
+    // Go embedded fields and interfaces
+    if let Some(go_type) = node.child_by_field_name("type") {
+        let members = match go_type.kind() {
+            "struct_type" => go_type.named_child(0),
+            "interface_type" => Some(go_type),
+            _ => None,
+        };
+        if let Some(members) = members {
+            let mut members_cursor = members.walk();
+            for member in members.named_children(&mut members_cursor) {
+                let embedded = match member.kind() {
+                    "field_declaration" if member.child_by_field_name("name").is_none() => {
+                        member.child_by_field_name("type")
+                    }
+                    "type_elem" | "constraint_elem" => member.named_child(0),
+                    _ => None,
+                };
+                implements.extend(embedded.map(|t| node_text(&t, source)));
+            }
+        }
+    }
 
-    if node.is_error() || node.is_missing() {
-        errors.push(ErrorNode {
-            message: "Syntax error".to_string(),
-            location: SourceLocation {
-                start_row: node.start_position().row,
-                start_column: node.start_position().column,
-                end_row: node.end_position().row,
-                end_column: node.end_position().column,
-            },
-            text: source[node.byte_range()].to_string(),
-        });
+    // Rust `impl Trait for Type` blocks anywhere in the file
+    if matches!(node.kind(), "struct_item" | "enum_item") {
+        if let Some(traits) = ctx.trait_impls.get(name) {
+            implements.extend(traits.iter().cloned());
+        }
     }
 
+    (extends, implements)
+}
+
+/// Collect `impl Trait for Type` blocks, keyed by the implementing type's name
+fn collect_trait_impls(node: &Node, source: &str, impls: &mut HashMap<String, Vec<String>>) {
     let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        errors.extend(find_error_nodes(&child, source));
+    for child in node.named_children(&mut cursor) {
+        if child.kind() == "impl_item" {
+            let trait_node = child.child_by_field_name("trait");
+            let type_name = child.child_by_field_name("type").and_then(|t| rust_type_name(&t, source));
+            if let (Some(trait_node), Some(type_name)) = (trait_node, type_name) {
+                impls.entry(type_name).or_default().push(node_text(&trait_node, source));
+            }
+        } else {
+            collect_trait_impls(&child, source, impls);
+        }
     }
+}
 
-    errors
+/// Base name of a Rust type (`Wrapper<T>` → `Wrapper`, `crate::a::B` → `B`)
+pub(crate) fn rust_type_name(node: &Node, source: &str) -> Option<String> {
+    match node.kind() {
+        "type_identifier" => Some(node_text(node, source)),
+        "generic_type" => rust_type_name(&node.child_by_field_name("type")?, source),
+        "scoped_type_identifier" => rust_type_name(&node.child_by_field_name("name")?, source),
+        _ => None,
+    }
+}
+
+/// Text of every named child of a node (type lists)
+fn named_children_text(node: &Node, source: &str) -> Vec<String> {
+    let mut cursor = node.walk();
+    let texts = node.named_children(&mut cursor).map(|n| node_text(&n, source)).collect();
+    texts
 }
 
-/// Extract information from an import node
-fn extract_import_info(node: &Node, source: &str) -> Option<ImportInfo> {
-    let kind = node.kind();
+/// Resolve the name of a function node
+///
+/// Most grammars expose a `name` field. C and C++ nest the name inside a
+/// chain of declarators instead (`int *ns::Calc::mul(int)`), so we walk down
+/// to the `function_declarator` and keep the unqualified name.
+pub(crate) fn function_name(node: &Node, source: &str) -> Option<String> {
+    if let Some(binding) = function_binding(node) {
+        return binding_name(&binding, source);
+    }
 
-    // Handle different import statement types
-    let (source_text, imported_names) = match kind {
-        "import_statement" | "import_declaration" => {
-            // JavaScript/TypeScript imports
-            let source_node = node.children(&mut node.walk()).find(|n| n.kind() == "string")?;
-            let source_text = source_node.utf8_text(source.as_bytes()).ok()?
-                .trim_matches('"').trim_matches('\'').to_string();
+    if let Some(name_node) = node.child_by_field_name("name") {
+        return name_node.utf8_text(source.as_bytes()).ok().map(|s| s.to_string());
+    }
 
-            let imported_names: Vec<String> = node.children(&mut node.walk())
-                .filter(|n| n.kind() == "identifier" || n.kind() == "property_identifier")
-                .filter_map(|n| n.utf8_text(source.as_bytes()).ok().map(|s| s.to_string()))
-                .collect();
+    let declarator = function_declarator(node)?;
+    let mut name_node = declarator.child_by_field_name("declarator")?;
+    while name_node.kind() == "qualified_identifier" {
+        name_node = name_node.child_by_field_name("name")?;
+    }
 
-            (source_text, imported_names)
-        }
-        "use_declaration" => {
-            // Rust use statements
-            let argument = node.child_by_field_name("argument")?;
-            let source_text = argument.utf8_text(source.as_bytes()).ok()?.to_string();
+    name_node.utf8_text(source.as_bytes()).ok().map(|s| s.to_string())
+}
 
-            let imported_names: Vec<String> = argument.children(&mut argument.walk())
-                .filter(|n| n.kind() == "identifier")
-                .filter_map(|n| n.utf8_text(source.as_bytes()).ok().map(|s| s.to_string()))
-                .collect();
+/// The node a function value is bound to: a `variable_declarator`, object
+/// `pair`, `assignment_expression` or class field
+pub(crate) fn function_binding<'tree>(node: &Node<'tree>) -> Option<Node<'tree>> {
+    if !matches!(node.kind(), "arrow_function" | "function_expression" | "function" | "generator_function") {
+        return None;
+    }
 
-            (source_text, imported_names)
-        }
+    let parent = node.parent()?;
+    let value_field = match parent.kind() {
+        "variable_declarator" | "pair" | "public_field_definition" | "field_definition" => "value",
+        "assignment_expression" => "right",
         _ => return None,
     };
 
-    let location = SourceLocation {
-        start_row: node.start_position().row,
-        start_column: node.start_position().column,
-        end_row: node.end_position().row,
-        end_column: node.end_position().column,
+    parent
+        .child_by_field_name(value_field)
+        .filter(|value| value.id() == node.id())
+        .map(|_| parent)
+}
+
+/// Name a function value takes from its binding
+fn binding_name(binding: &Node, source: &str) -> Option<String> {
+    let name_node = match binding.kind() {
+        "pair" => binding.child_by_field_name("key")?,
+        "field_definition" => binding.child_by_field_name("property")?,
+        // `exports.handler = ...`, `this.onClick = ...`
+        "assignment_expression" => {
+            let left = binding.child_by_field_name("left")?;
+            match left.kind() {
+                "member_expression" => left.child_by_field_name("property")?,
+                "identifier" => left,
+                _ => return None,
+            }
+        }
+        _ => binding.child_by_field_name("name").filter(|n| n.kind() != "object_pattern" && n.kind() != "array_pattern")?,
+    };
+
+    let name = node_text(&name_node, source);
+    Some(name.trim_matches(|c| c == '"' || c == '\'').to_string())
+}
+
+/// Find the `function_declarator` of a C/C++ definition or declaration,
+/// looking through pointer and reference declarators
+pub(crate) fn function_declarator<'tree>(node: &Node<'tree>) -> Option<Node<'tree>> {
+    let mut current = node.child_by_field_name("declarator")?;
+    while current.kind() != "function_declarator" {
+        current = current.child_by_field_name("declarator")?;
+    }
+    Some(current)
+}
+
+/// Resolve the name of a class-like node
+///
+/// Anonymous C structs are usually named by a surrounding typedef
+/// (`typedef struct { ... } Point;`), so fall back to that declarator.
+pub(crate) fn class_name(node: &Node, source: &str) -> Option<String> {
+    let name_node = match node.child_by_field_name("name") {
+        Some(name_node) => name_node,
+        None => {
+            let parent = node.parent().filter(|p| p.kind() == "type_definition")?;
+            parent.child_by_field_name("declarator")?
+        }
     };
 
-    Some(ImportInfo {
-        source: source_text,
-        imported_names,
-        is_type_only: kind.contains("type"),
-        location,
-    })
+    name_node.utf8_text(source.as_bytes()).ok().map(|s| s.to_string())
 }
@@ -0,0 +1,337 @@
+//! Identifier reference index
+//!
+//! Every identifier in a file is classified once as a definition or a
+//! reference, and as a type, value or property name. Chunks aggregate the
+//! occurrences within their lines into deduplicated, counted lists.
+
+use crate::types::{IdentifierCount, IdentifierKind};
+use std::collections::HashMap;
+use tree_sitter::{Node, TreeCursor};
+
+/// Leaf nodes that name something
+const IDENTIFIER_NODES: &[&str] = &[
+    "identifier",
+    "type_identifier",
+    "property_identifier",
+    "private_property_identifier",
+    "field_identifier",
+    "shorthand_property_identifier",
+    "shorthand_property_identifier_pattern",
+    "namespace_identifier",
+    "package_identifier",
+];
+
+/// Fields whose subtree never declares the enclosing definition's name
+const REFERENCE_FIELDS: &[&str] = &[
+    "type",
+    "return_type",
+    "value",
+    "right",
+    "default",
+    "parameters",
+    "arguments",
+    "body",
+    "key",
+    "object",
+    "operand",
+    "function",
+    "scope",
+    "path",
+    "superclass",
+    "index",
+];
+
+/// `(parent kind, field)` slots that declare a name; `None` matches every
+/// child, as in parameter lists
+const DEFINITION_SLOTS: &[(&str, Option<&str>)] = &[
+    // Functions and methods
+    ("function_declaration", Some("name")),
+    ("generator_function_declaration", Some("name")),
+    ("function_definition", Some("name")),
+    ("function_definition", Some("declarator")),
+    ("function_item", Some("name")),
+    ("function_signature_item", Some("name")),
+    ("method_definition", Some("name")),
+    ("method_declaration", Some("name")),
+    ("method_signature", Some("name")),
+    ("abstract_method_signature", Some("name")),
+    ("constructor_declaration", Some("name")),
+    // Types
+    ("class_declaration", Some("name")),
+    ("abstract_class_declaration", Some("name")),
+    ("class", Some("name")),
+    ("class_definition", Some("name")),
+    ("interface_declaration", Some("name")),
+    ("type_alias_declaration", Some("name")),
+    ("enum_declaration", Some("name")),
+    ("record_declaration", Some("name")),
+    ("struct_item", Some("name")),
+    ("enum_item", Some("name")),
+    ("union_item", Some("name")),
+    ("trait_item", Some("name")),
+    ("type_item", Some("name")),
+    ("type_spec", Some("name")),
+    ("type_definition", Some("declarator")),
+    ("type_parameter", Some("name")),
+    ("constrained_type_parameter", Some("left")),
+    ("struct_specifier", Some("name")),
+    ("class_specifier", Some("name")),
+    ("union_specifier", Some("name")),
+    ("enum_specifier", Some("name")),
+    // Members
+    ("public_field_definition", Some("name")),
+    ("field_definition", Some("property")),
+    ("field_declaration", Some("name")),
+    ("field_declaration", Some("declarator")),
+    ("enum_body", Some("name")),
+    ("enum_assignment", Some("name")),
+    ("enum_variant", Some("name")),
+    ("enum_constant", Some("name")),
+    ("enumerator", Some("name")),
+    // Modules
+    ("internal_module", Some("name")),
+    ("module", Some("name")),
+    ("mod_item", Some("name")),
+    ("namespace_definition", Some("name")),
+    // Variables and constants
+    ("variable_declarator", Some("name")),
+    ("init_declarator", Some("declarator")),
+    ("declaration", Some("declarator")),
+    ("let_declaration", Some("pattern")),
+    ("const_item", Some("name")),
+    ("static_item", Some("name")),
+    ("const_spec", Some("name")),
+    ("var_spec", Some("name")),
+    ("short_var_declaration", Some("left")),
+    ("assignment", Some("left")),
+    ("for_statement", Some("left")),
+    ("for_in_statement", Some("left")),
+    ("for_in_clause", Some("left")),
+    ("for_expression", Some("pattern")),
+    ("as_pattern", Some("alias")),
+    ("catch_clause", Some("parameter")),
+    // Parameters
+    ("formal_parameters", None),
+    ("parameters", None),
+    ("lambda_parameters", None),
+    ("closure_parameters", None),
+    ("arrow_function", Some("parameter")),
+    ("required_parameter", Some("pattern")),
+    ("optional_parameter", Some("pattern")),
+    ("formal_parameter", Some("name")),
+    ("parameter", Some("pattern")),
+    ("parameter_declaration", Some("name")),
+    ("parameter_declaration", Some("declarator")),
+    ("variadic_parameter_declaration", Some("name")),
+];
+
+/// Declarations that introduce type names
+const TYPE_DECLARATIONS: &[&str] = &[
+    "class_declaration",
+    "abstract_class_declaration",
+    "class",
+    "class_definition",
+    "interface_declaration",
+    "type_alias_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "struct_item",
+    "enum_item",
+    "union_item",
+    "trait_item",
+    "type_item",
+    "type_spec",
+    "type_definition",
+    "type_parameter",
+    "constrained_type_parameter",
+    "struct_specifier",
+    "class_specifier",
+    "union_specifier",
+    "enum_specifier",
+];
+
+/// Declarations that introduce member names
+const MEMBER_DECLARATIONS: &[&str] = &[
+    "method_definition",
+    "method_declaration",
+    "method_signature",
+    "abstract_method_signature",
+    "constructor_declaration",
+    "public_field_definition",
+    "field_definition",
+    "field_declaration",
+    "enum_body",
+    "enum_assignment",
+    "enum_variant",
+    "enum_constant",
+    "enumerator",
+];
+
+/// Receivers that never name a symbol of their own
+const IGNORED_NAMES: &[&str] = &["self", "this", "_"];
+
+/// A single identifier in the source
+#[derive(Debug, Clone)]
+pub(crate) struct Occurrence {
+    pub(crate) name: String,
+    pub(crate) kind: IdentifierKind,
+    pub(crate) line: usize,
+    pub(crate) is_definition: bool,
+}
+
+/// Where the walk currently is
+#[derive(Clone, Copy, Default)]
+struct Scope {
+    /// Kind of the name being declared, inside a definition slot
+    definition: Option<IdentifierKind>,
+    /// Inside a type annotation
+    in_type: bool,
+}
+
+/// Classify every identifier in a subtree, in source order
+pub(crate) fn extract_identifiers(root: &Node, source: &str) -> Vec<Occurrence> {
+    let mut occurrences = Vec::new();
+    let mut cursor = root.walk();
+    visit(&mut cursor, source, Scope::default(), &mut occurrences);
+    occurrences
+}
+
+/// Count the definitions and references within the given 1-based,
+/// inclusive lines, in order of first occurrence
+///
+/// Names defined within the lines are not references, so a local that
+/// shadows an import is not counted as a use of it.
+pub(crate) fn identifiers_in_lines(
+    occurrences: &[Occurrence],
+    start_line: usize,
+    end_line: usize,
+) -> (Vec<IdentifierCount>, Vec<IdentifierCount>) {
+    let mut defines = Vec::new();
+    let mut references = Vec::new();
+    let mut positions: HashMap<(bool, &str, IdentifierKind), usize> = HashMap::new();
+
+    for occurrence in occurrences
+        .iter()
+        .filter(|o| o.line >= start_line && o.line <= end_line)
+    {
+        let counts: &mut Vec<IdentifierCount> = if occurrence.is_definition { &mut defines } else { &mut references };
+        let key = (occurrence.is_definition, occurrence.name.as_str(), occurrence.kind);
+        match positions.get(&key) {
+            Some(&index) => counts[index].count += 1,
+            None => {
+                positions.insert(key, counts.len());
+                counts.push(IdentifierCount {
+                    name: occurrence.name.clone(),
+                    kind: occurrence.kind,
+                    count: 1,
+                });
+            }
+        }
+    }
+
+    references.retain(|reference| !positions.contains_key(&(true, reference.name.as_str(), reference.kind)));
+    (defines, references)
+}
+
+fn visit(cursor: &mut TreeCursor, source: &str, scope: Scope, occurrences: &mut Vec<Occurrence>) {
+    let node = cursor.node();
+    if !cursor.goto_first_child() {
+        return;
+    }
+
+    loop {
+        let child = cursor.node();
+        let field = cursor.field_name();
+
+        let child_scope = enter(&node, field, &child, scope);
+        if IDENTIFIER_NODES.contains(&child.kind()) {
+            if let Some(occurrence) = classify(&child, &node, field, child_scope, source) {
+                occurrences.push(occurrence);
+            }
+        } else {
+            visit(cursor, source, child_scope, occurrences);
+        }
+
+        if !cursor.goto_next_sibling() {
+            break;
+        }
+    }
+    cursor.goto_parent();
+}
+
+/// Scope of a child reached from `parent` through `field`
+fn enter(parent: &Node, field: Option<&str>, child: &Node, scope: Scope) -> Scope {
+    let mut scope = scope;
+
+    if field.is_some_and(|f| REFERENCE_FIELDS.contains(&f)) {
+        scope.definition = None;
+    }
+    if let Some(kind) = definition_kind(parent, field) {
+        scope.definition = Some(kind);
+    }
+    if matches!(field, Some("type") | Some("return_type"))
+        || matches!(child.kind(), "type_annotation" | "type_arguments" | "type")
+    {
+        scope.in_type = true;
+    }
+
+    scope
+}
+
+/// Kind of the name declared in a definition slot, if `field` is one
+fn definition_kind(parent: &Node, field: Option<&str>) -> Option<IdentifierKind> {
+    let kind = parent.kind();
+    let is_slot = DEFINITION_SLOTS
+        .iter()
+        .any(|&(slot_kind, slot_field)| slot_kind == kind && (slot_field.is_none() || slot_field == field));
+    // `struct point p;` names a type without defining it
+    let is_forward = kind.ends_with("_specifier") && parent.child_by_field_name("body").is_none();
+    if !is_slot || is_forward {
+        return None;
+    }
+
+    // Java fields: `private int count;`
+    let is_field = kind == "variable_declarator" && parent.parent().is_some_and(|p| p.kind() == "field_declaration");
+
+    Some(if TYPE_DECLARATIONS.contains(&kind) {
+        IdentifierKind::Type
+    } else if MEMBER_DECLARATIONS.contains(&kind) || is_field {
+        IdentifierKind::Property
+    } else {
+        IdentifierKind::Value
+    })
+}
+
+/// Classify one identifier leaf
+fn classify(node: &Node, parent: &Node, field: Option<&str>, scope: Scope, source: &str) -> Option<Occurrence> {
+    let name = &source[node.byte_range()];
+    if name.is_empty() || IGNORED_NAMES.contains(&name) {
+        return None;
+    }
+
+    let definition = scope.definition;
+    let is_property = matches!(node.kind(), "property_identifier" | "private_property_identifier" | "field_identifier")
+        || matches!(
+            (parent.kind(), field),
+            ("attribute", Some("attribute")) | ("field_access", Some("field")) | ("keyword_argument", Some("name"))
+        )
+        || (parent.kind() == "method_invocation"
+            && field == Some("name")
+            && parent.child_by_field_name("object").is_some());
+
+    let kind = match definition {
+        Some(_) if node.kind() == "type_identifier" => IdentifierKind::Type,
+        Some(_) if is_property => IdentifierKind::Property,
+        Some(kind) => kind,
+        None if is_property => IdentifierKind::Property,
+        None if node.kind() == "type_identifier" || scope.in_type => IdentifierKind::Type,
+        None => IdentifierKind::Value,
+    };
+
+    Some(Occurrence {
+        name: name.to_string(),
+        kind,
+        line: node.start_position().row + 1,
+        is_definition: definition.is_some(),
+    })
+}
@@ -0,0 +1,86 @@
+//! Deterministic chunk identity
+//!
+//! Chunk ids are derived from the file path, the qualified name of the
+//! chunk's symbol and a hash of its normalized content, so re-indexing an
+//! unchanged file reproduces the same ids. Each chunk also carries a stable
+//! id that ignores the content, which survives edits and moves within the
+//! file, and the content hash itself, which changes only when the code does.
+
+use crate::types::CodeChunk;
+use sha1_smol::Sha1;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Namespace of all chunk ids
+const CHUNK_NAMESPACE: Uuid = Uuid::from_u128(0x6c1b_5e0f_27a4_4d0e_9a3b_58f2_c1d7_e904);
+
+/// Assign the ids of a file's chunks
+///
+/// Named chunks are identified by their symbol; the rest by their content,
+/// since top-level code has nothing more stable to go by. Chunks that share
+/// an identity (overloads, repeated code) are numbered in source order.
+pub(crate) fn assign_ids(chunks: &mut [CodeChunk], file_path: &str) {
+    let mut order: Vec<usize> = (0..chunks.len()).collect();
+    order.sort_by_key(|&index| (chunks[index].start_line, chunks[index].end_line));
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for index in order {
+        let chunk = &mut chunks[index];
+        chunk.content_hash = own_content_hash(chunk);
+
+        let base = match (&chunk.qualified_name, chunk.kind) {
+            (Some(name), Some(kind)) => format!("{}:{}", kind.as_str(), name),
+            _ => format!("content:{}", chunk.content_hash),
+        };
+        let count = seen.entry(base.clone()).or_insert(0);
+        *count += 1;
+        let key = if *count == 1 { base } else { format!("{}#{}", base, count) };
+
+        let stable_id = Uuid::new_v5(&CHUNK_NAMESPACE, format!("{}\n{}", file_path, key).as_bytes());
+        chunk.id = Uuid::new_v5(&stable_id, chunk.content_hash.as_bytes()).to_string();
+        chunk.stable_id = stable_id.to_string();
+    }
+}
+
+/// Assign the ids of the `index`th piece of a split chunk
+pub(crate) fn assign_part_ids(part: &mut CodeChunk, parent: &CodeChunk, index: usize) {
+    let parent_id = Uuid::parse_str(&parent.stable_id).unwrap_or(CHUNK_NAMESPACE);
+    let stable_id = Uuid::new_v5(&parent_id, format!("part:{}", index).as_bytes());
+
+    part.content_hash = own_content_hash(part);
+    part.id = Uuid::new_v5(&stable_id, part.content_hash.as_bytes()).to_string();
+    part.stable_id = stable_id.to_string();
+}
+
+/// Hash of the lines a chunk doesn't repeat from the chunk before it, so
+/// that editing one piece of split code leaves the next piece's ids alone
+fn own_content_hash(chunk: &CodeChunk) -> String {
+    let repeated = chunk.overlap.map_or(0, |range| range.end_line - range.start_line + 1);
+    let own: Vec<&str> = chunk.text.lines().skip(repeated).collect();
+    content_hash(&own.join("\n"))
+}
+
+/// SHA-1 of code with its layout normalized
+///
+/// Line endings, trailing whitespace, blank lines and the indentation shared
+/// by every line do not affect the hash, so re-indenting a function or
+/// adding blank lines around it does not count as a change.
+pub fn content_hash(text: &str) -> String {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let indent = lines
+        .iter()
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+
+    let mut hasher = Sha1::new();
+    for line in lines {
+        hasher.update(&line.as_bytes()[indent..]);
+        hasher.update(b"\n");
+    }
+    hasher.digest().to_string()
+}
@@ -0,0 +1,469 @@
+//! Import extraction
+//!
+//! Every import form is normalised to an `ImportInfo`: the module specifier
+//! as written plus one `ImportBinding` per local name it introduces.
+
+use crate::extractor::node_text;
+use crate::types::{BindingKind, ImportBinding, ImportInfo, ImportKind, SourceLocation};
+use tree_sitter::Node;
+
+/// Extract all imports from the AST
+pub fn extract_imports(root: &Node, source: &str, language: &str) -> Vec<ImportInfo> {
+    let mut imports = Vec::new();
+    collect_imports(root, source, language, &mut imports);
+    imports
+}
+
+/// Walk the tree and find import statements
+fn collect_imports(node: &Node, source: &str, language: &str, imports: &mut Vec<ImportInfo>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match (language, child.kind()) {
+            ("python", "import_statement") => imports.extend(python_import(&child, source)),
+            ("python", "import_from_statement") => imports.extend(python_from_import(&child, source)),
+            (_, "import_statement") => imports.extend(js_import(&child, source)),
+            (_, "export_statement") if child.child_by_field_name("source").is_some() => {
+                imports.extend(js_reexport(&child, source));
+            }
+            ("go", "import_declaration") => go_imports(&child, source, imports),
+            ("java", "import_declaration") => imports.extend(java_import(&child, source)),
+            (_, "use_declaration") => rust_use(&child, source, imports),
+            (_, "preproc_include") => imports.extend(c_include(&child, source)),
+            (_, "call_expression") => {
+                imports.extend(js_call_import(&child, source));
+                // Arguments may hold further imports: `wrap(require('x'))`
+                collect_imports(&child, source, language, imports);
+            }
+            _ => collect_imports(&child, source, language, imports),
+        }
+    }
+}
+
+/// `import a, * as ns from 'm'`, `import { x as y } from 'm'`,
+/// `import './side-effect'`, TS `import q = require('m')`
+fn js_import(node: &Node, source: &str) -> Option<ImportInfo> {
+    if let Some(clause) = child_of_kind(node, "import_require_clause") {
+        let specifier = string_value(&clause.child_by_field_name("source")?, source);
+        let local = child_of_kind(&clause, "identifier").map(|n| node_text(&n, source))?;
+        let bindings = vec![binding("*", &local, BindingKind::Namespace)];
+        return Some(import_info(node, specifier, bindings, ImportKind::Require, 0));
+    }
+
+    let specifier = string_value(&node.child_by_field_name("source")?, source);
+    let mut bindings = Vec::new();
+
+    if let Some(clause) = child_of_kind(node, "import_clause") {
+        let mut cursor = clause.walk();
+        for part in clause.named_children(&mut cursor) {
+            match part.kind() {
+                "identifier" => bindings.push(binding("default", &node_text(&part, source), BindingKind::Default)),
+                "namespace_import" => {
+                    if let Some(local) = child_of_kind(&part, "identifier") {
+                        bindings.push(binding("*", &node_text(&local, source), BindingKind::Namespace));
+                    }
+                }
+                "named_imports" => bindings.extend(js_specifiers(&part, source)),
+                _ => {}
+            }
+        }
+    }
+
+    Some(type_only_if(has_type_keyword(node), import_info(node, specifier, bindings, ImportKind::Static, 0)))
+}
+
+/// `export * from 'm'`, `export * as ns from 'm'`, `export { a as b } from 'm'`
+fn js_reexport(node: &Node, source: &str) -> Option<ImportInfo> {
+    let specifier = string_value(&node.child_by_field_name("source")?, source);
+
+    let bindings = if let Some(clause) = child_of_kind(node, "export_clause") {
+        js_specifiers(&clause, source)
+    } else if let Some(namespace) = child_of_kind(node, "namespace_export") {
+        let local = child_of_kind(&namespace, "identifier").map(|n| node_text(&n, source)).unwrap_or_default();
+        vec![binding("*", &local, BindingKind::Namespace)]
+    } else {
+        vec![binding("*", "*", BindingKind::Wildcard)]
+    };
+
+    Some(type_only_if(has_type_keyword(node), import_info(node, specifier, bindings, ImportKind::Static, 0)))
+}
+
+/// `{ a, b as c }` in import and export clauses
+fn js_specifiers(node: &Node, source: &str) -> Vec<ImportBinding> {
+    let mut cursor = node.walk();
+    let specifiers = node
+        .named_children(&mut cursor)
+        .filter(|n| n.kind() == "import_specifier" || n.kind() == "export_specifier")
+        .filter_map(|specifier| {
+            let imported = specifier.child_by_field_name("name").map(|n| string_value(&n, source))?;
+            let local = specifier
+                .child_by_field_name("alias")
+                .map_or_else(|| imported.clone(), |n| string_value(&n, source));
+            let kind = if imported == "default" { BindingKind::Default } else { BindingKind::Named };
+            // `import { type A }` erases only this specifier
+            Some(ImportBinding {
+                is_type_only: has_type_keyword(&specifier),
+                ..binding(&imported, &local, kind)
+            })
+        })
+        .collect();
+    specifiers
+}
+
+/// `require('m')` and dynamic `import('m')`, with the bindings of the
+/// declaration they initialise
+fn js_call_import(node: &Node, source: &str) -> Option<ImportInfo> {
+    let function = node.child_by_field_name("function")?;
+    let kind = match function.kind() {
+        "import" => ImportKind::Dynamic,
+        "identifier" if node_text(&function, source) == "require" => ImportKind::Require,
+        _ => return None,
+    };
+
+    let argument = node.child_by_field_name("arguments")?.named_child(0)?;
+    if argument.kind() != "string" && argument.kind() != "template_string" {
+        return None;
+    }
+    let specifier = string_value(&argument, source);
+
+    // Look through `await import(...)` and `require('m').member`
+    let mut value = *node;
+    let mut member = None;
+    while let Some(parent) = value.parent() {
+        match parent.kind() {
+            "await_expression" | "parenthesized_expression" => value = parent,
+            "member_expression" if member.is_none() => {
+                member = parent.child_by_field_name("property").map(|p| node_text(&p, source));
+                value = parent;
+            }
+            _ => break,
+        }
+    }
+
+    let target = value
+        .parent()
+        .filter(|p| p.kind() == "variable_declarator")
+        .and_then(|declarator| declarator.child_by_field_name("name"));
+
+    let bindings = match (target, member) {
+        (Some(name), Some(member)) if name.kind() == "identifier" => {
+            vec![binding(&member, &node_text(&name, source), BindingKind::Named)]
+        }
+        (Some(name), None) if name.kind() == "identifier" => {
+            vec![binding("*", &node_text(&name, source), BindingKind::Namespace)]
+        }
+        (Some(pattern), None) if pattern.kind() == "object_pattern" => destructured_bindings(&pattern, source),
+        _ => Vec::new(),
+    };
+
+    Some(import_info(node, specifier, bindings, kind, 0))
+}
+
+/// `const { a, b: c } = require('m')`
+fn destructured_bindings(pattern: &Node, source: &str) -> Vec<ImportBinding> {
+    let mut cursor = pattern.walk();
+    let bindings = pattern
+        .named_children(&mut cursor)
+        .filter_map(|property| match property.kind() {
+            "shorthand_property_identifier_pattern" => {
+                let name = node_text(&property, source);
+                Some(binding(&name, &name, BindingKind::Named))
+            }
+            "pair_pattern" => {
+                let imported = property.child_by_field_name("key").map(|k| node_text(&k, source))?;
+                let local = property.child_by_field_name("value").map(|v| node_text(&v, source))?;
+                Some(binding(&imported, &local, BindingKind::Named))
+            }
+            _ => None,
+        })
+        .collect();
+    bindings
+}
+
+/// `import os.path, numpy as np`: one import per module
+fn python_import(node: &Node, source: &str) -> Vec<ImportInfo> {
+    let mut cursor = node.walk();
+    let imports = node
+        .children_by_field_name("name", &mut cursor)
+        .filter_map(|name| {
+            let (module, alias) = python_alias(&name, source)?;
+            // `import os.path` binds `os`
+            let local = alias.unwrap_or_else(|| module.split('.').next().unwrap_or(&module).to_string());
+            let bindings = vec![binding("*", &local, BindingKind::Namespace)];
+            let info = import_info(node, module, bindings, ImportKind::Static, 0);
+            Some(type_only_if(in_type_checking_block(node, source), info))
+        })
+        .collect();
+    imports
+}
+
+/// `from ..models import User as U, Admin`, `from x import *`
+fn python_from_import(node: &Node, source: &str) -> Option<ImportInfo> {
+    let module = node.child_by_field_name("module_name")?;
+    let relative_level = if module.kind() == "relative_import" {
+        child_of_kind(&module, "import_prefix").map_or(0, |prefix| node_text(&prefix, source).len())
+    } else {
+        0
+    };
+
+    let mut bindings = Vec::new();
+    if child_of_kind(node, "wildcard_import").is_some() {
+        bindings.push(binding("*", "*", BindingKind::Wildcard));
+    }
+
+    let mut cursor = node.walk();
+    for name in node.children_by_field_name("name", &mut cursor) {
+        if let Some((imported, alias)) = python_alias(&name, source) {
+            let local = alias.unwrap_or_else(|| imported.clone());
+            bindings.push(binding(&imported, &local, BindingKind::Named));
+        }
+    }
+
+    let info = import_info(node, node_text(&module, source), bindings, ImportKind::Static, relative_level);
+    Some(type_only_if(in_type_checking_block(node, source), info))
+}
+
+/// Name and optional alias of a Python `dotted_name` or `aliased_import`
+fn python_alias(node: &Node, source: &str) -> Option<(String, Option<String>)> {
+    match node.kind() {
+        "dotted_name" => Some((node_text(node, source), None)),
+        "aliased_import" => {
+            let name = node.child_by_field_name("name")?;
+            let alias = node.child_by_field_name("alias").map(|a| node_text(&a, source));
+            Some((node_text(&name, source), alias))
+        }
+        _ => None,
+    }
+}
+
+/// Go `import f "fmt"`, `import _ "lib/pq"`, `import . "math"`, grouped or not
+fn go_imports(node: &Node, source: &str, imports: &mut Vec<ImportInfo>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "import_spec" => imports.extend(go_import_spec(&child, source)),
+            "import_spec_list" => go_imports(&child, source, imports),
+            _ => {}
+        }
+    }
+}
+
+/// A single Go import spec
+fn go_import_spec(node: &Node, source: &str) -> Option<ImportInfo> {
+    let path = string_value(&node.child_by_field_name("path")?, source);
+    let package = path.rsplit('/').next().unwrap_or(&path).to_string();
+
+    let bindings = match node.child_by_field_name("name") {
+        // Blank imports only run the package's init functions
+        Some(name) if name.kind() == "blank_identifier" => Vec::new(),
+        Some(name) if name.kind() == "dot" => vec![binding("*", "*", BindingKind::Wildcard)],
+        Some(name) => vec![binding("*", &node_text(&name, source), BindingKind::Namespace)],
+        None => vec![binding("*", &package, BindingKind::Namespace)],
+    };
+
+    Some(import_info(node, path, bindings, ImportKind::Static, 0))
+}
+
+/// Java `import a.b.C;`, `import a.b.*;`, `import static a.b.C.m;`
+fn java_import(node: &Node, source: &str) -> Option<ImportInfo> {
+    let path = child_of_kind(node, "scoped_identifier").or_else(|| child_of_kind(node, "identifier"))?;
+    let path = node_text(&path, source);
+
+    if child_of_kind(node, "asterisk").is_some() {
+        let bindings = vec![binding("*", "*", BindingKind::Wildcard)];
+        return Some(import_info(node, path, bindings, ImportKind::Static, 0));
+    }
+
+    let (package, name) = path.rsplit_once('.').unwrap_or(("", &path));
+    let bindings = vec![binding(name, name, BindingKind::Named)];
+    Some(import_info(node, package.to_string(), bindings, ImportKind::Static, 0))
+}
+
+/// Rust `use` declarations, with nested trees flattened into one import
+/// per module path: `use std::{io::{self, Read}, fmt::Result as R}`
+fn rust_use(node: &Node, source: &str, imports: &mut Vec<ImportInfo>) {
+    let Some(argument) = node.child_by_field_name("argument") else {
+        return;
+    };
+
+    let mut leaves = Vec::new();
+    flatten_use_tree(&argument, &[], source, &mut leaves);
+
+    for (path, leaf) in leaves {
+        let module = path.join("::");
+        match imports.last_mut() {
+            Some(last) if last.source == module && same_node(&last.location, node) => {
+                if leaf.kind != BindingKind::Wildcard {
+                    last.imported_names.push(leaf.local.clone());
+                }
+                last.bindings.push(leaf);
+            }
+            _ => imports.push(import_info(node, module, vec![leaf], ImportKind::Static, 0)),
+        }
+    }
+}
+
+/// Collect the (module path, binding) leaves of a Rust use tree
+pub(crate) fn flatten_use_tree(node: &Node, prefix: &[String], source: &str, leaves: &mut Vec<(Vec<String>, ImportBinding)>) {
+    let path_of = |path: Option<Node>| -> Vec<String> {
+        let mut full = prefix.to_vec();
+        if let Some(path) = path {
+            full.extend(node_text(&path, source).split("::").map(|s| s.trim().to_string()));
+        }
+        full
+    };
+
+    match node.kind() {
+        "use_list" => {
+            let mut cursor = node.walk();
+            for item in node.named_children(&mut cursor) {
+                flatten_use_tree(&item, prefix, source, leaves);
+            }
+        }
+        "scoped_use_list" => {
+            let path = path_of(node.child_by_field_name("path"));
+            if let Some(list) = node.child_by_field_name("list") {
+                flatten_use_tree(&list, &path, source, leaves);
+            }
+        }
+        "use_wildcard" => {
+            let path = path_of(node.named_child(0));
+            leaves.push((path, binding("*", "*", BindingKind::Wildcard)));
+        }
+        "use_as_clause" => {
+            let mut path = path_of(node.child_by_field_name("path"));
+            let alias = node.child_by_field_name("alias").map(|a| node_text(&a, source));
+            if let Some(name) = path.pop() {
+                let local = alias.unwrap_or_else(|| name.clone());
+                leaves.push(rust_leaf(path, &name, &local));
+            }
+        }
+        _ => {
+            // `self` re-imports the module itself: `use std::io::{self}`
+            let mut path = path_of(Some(*node));
+            if path.last().is_some_and(|last| last == "self") {
+                path.pop();
+            }
+            if let Some(name) = path.pop() {
+                leaves.push(rust_leaf(path, &name, &name));
+            }
+        }
+    }
+}
+
+/// A Rust use leaf; a bare `use serde;` imports the crate itself
+fn rust_leaf(path: Vec<String>, name: &str, local: &str) -> (Vec<String>, ImportBinding) {
+    if path.is_empty() {
+        (vec![name.to_string()], binding("*", local, BindingKind::Namespace))
+    } else {
+        (path, binding(name, local, BindingKind::Named))
+    }
+}
+
+/// C/C++ includes: `#include "local.h"` or `#include <vector>`
+fn c_include(node: &Node, source: &str) -> Option<ImportInfo> {
+    let path = node.child_by_field_name("path")?;
+    let specifier = node_text(&path, source)
+        .trim_matches(|c| c == '"' || c == '<' || c == '>')
+        .to_string();
+    Some(import_info(node, specifier, Vec::new(), ImportKind::Static, 0))
+}
+
+/// Build an `ImportInfo`, deriving `imported_names` from the bindings
+fn import_info(
+    node: &Node,
+    specifier: String,
+    bindings: Vec<ImportBinding>,
+    kind: ImportKind,
+    relative_level: usize,
+) -> ImportInfo {
+    let imported_names = bindings
+        .iter()
+        .filter(|b| b.kind != BindingKind::Wildcard)
+        .map(|b| b.local.clone())
+        .collect();
+
+    ImportInfo {
+        source: specifier,
+        imported_names,
+        bindings,
+        kind,
+        relative_level,
+        is_type_only: false,
+        location: SourceLocation {
+            start_row: node.start_position().row,
+            start_column: node.start_position().column,
+            end_row: node.end_position().row,
+            end_column: node.end_position().column,
+        },
+    }
+}
+
+fn binding(imported: &str, local: &str, kind: BindingKind) -> ImportBinding {
+    ImportBinding {
+        imported: imported.to_string(),
+        local: local.to_string(),
+        kind,
+        is_type_only: false,
+    }
+}
+
+/// Mark a whole import as type-only, including every binding
+///
+/// Statement-level only: `import { type A } from 'm'` keeps the module
+/// import itself, so only its specifiers are flagged.
+fn type_only_if(is_type_only: bool, mut info: ImportInfo) -> ImportInfo {
+    if is_type_only {
+        info.is_type_only = true;
+        for binding in &mut info.bindings {
+            binding.is_type_only = true;
+        }
+    }
+    info
+}
+
+/// TS `import type`, `export type`, `import { type A }` and Flow `import typeof`
+pub(crate) fn has_type_keyword(node: &Node) -> bool {
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).any(|n| n.kind() == "type" || n.kind() == "typeof");
+    found
+}
+
+/// Python imports guarded by `if TYPE_CHECKING:` never run
+fn in_type_checking_block(node: &Node, source: &str) -> bool {
+    let mut current = *node;
+    while let Some(parent) = current.parent() {
+        if parent.kind() == "if_statement" {
+            let guarded = parent.child_by_field_name("condition").is_some_and(|condition| {
+                let condition = node_text(&condition, source);
+                condition == "TYPE_CHECKING" || condition.ends_with(".TYPE_CHECKING")
+            });
+            let in_consequence = parent
+                .child_by_field_name("consequence")
+                .is_some_and(|consequence| consequence.id() == current.id());
+            if guarded && in_consequence {
+                return true;
+            }
+        }
+        current = parent;
+    }
+    false
+}
+
+/// Whether an import was built from the given node
+fn same_node(location: &SourceLocation, node: &Node) -> bool {
+    location.start_row == node.start_position().row && location.start_column == node.start_position().column
+}
+
+/// First named child of the given kind
+pub(crate) fn child_of_kind<'tree>(node: &Node<'tree>, kind: &str) -> Option<Node<'tree>> {
+    let mut cursor = node.walk();
+    let child = node.named_children(&mut cursor).find(|n| n.kind() == kind);
+    child
+}
+
+/// Contents of a string literal without its quotes
+pub(crate) fn string_value(node: &Node, source: &str) -> String {
+    node_text(node, source)
+        .trim_matches(|c| c == '"' || c == '\'' || c == '`')
+        .to_string()
+}
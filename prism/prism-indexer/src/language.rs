@@ -34,7 +34,7 @@ pub struct LanguageConfig {
 }
 
 impl LanguageConfig {
-    /// Get configuration for TypeScript/JavaScript
+    /// Get configuration for TypeScript/JavaScript (including TSX/JSX)
     pub fn typescript() -> Self {
         LanguageConfig {
             preferred_chunk_size: 512,
@@ -172,6 +172,30 @@ impl LanguageConfig {
         }
     }
 
+    /// Get configuration for C
+    pub fn c() -> Self {
+        LanguageConfig {
+            preferred_chunk_size: 512,
+            max_lines: 200,
+            include_docs: true,
+            include_imports: true,
+            function_nodes: vec![
+                "function_definition",
+                "function_declarator",
+            ],
+            class_nodes: vec![
+                "struct_specifier",
+                "union_specifier",
+            ],
+            interface_nodes: vec![
+                // C doesn't have interfaces
+            ],
+            import_nodes: vec![
+                "preproc_include",
+            ],
+        }
+    }
+
     /// Get configuration for C++
     pub fn cpp() -> Self {
         LanguageConfig {
@@ -193,7 +217,7 @@ impl LanguageConfig {
                 // C++ doesn't have formal interfaces
             ],
             import_nodes: vec![
-                "include_declaration",
+                "preproc_include",
                 "using_declaration",
             ],
         }
@@ -223,11 +247,12 @@ impl LanguageConfig {
 /// Get language configuration for a given language
 pub fn get_language_config(language: &str) -> LanguageConfig {
     match language {
-        "typescript" | "javascript" => LanguageConfig::typescript(),
+        "typescript" | "javascript" | "tsx" | "jsx" => LanguageConfig::typescript(),
         "python" => LanguageConfig::python(),
         "rust" => LanguageConfig::rust(),
         "go" => LanguageConfig::go(),
         "java" => LanguageConfig::java(),
+        "c" => LanguageConfig::c(),
         "cpp" | "c++" => LanguageConfig::cpp(),
         _ => LanguageConfig::typescript(), // Default to TypeScript
     }
@@ -237,11 +262,12 @@ pub fn get_language_config(language: &str) -> LanguageConfig {
 pub fn is_supported_language(language: &str) -> bool {
     matches!(
         language,
-        "typescript" | "javascript" | "python" | "rust" | "go" | "java" | "cpp"
+        "typescript" | "javascript" | "tsx" | "jsx" | "python" | "rust" | "go" | "java" |
+        "c" | "cpp" | "c++"
     )
 }
 
 /// Get list of supported languages
 pub fn supported_languages() -> Vec<&'static str> {
-    vec!["typescript", "javascript", "python", "rust", "go", "java", "cpp"]
+    vec!["typescript", "javascript", "tsx", "jsx", "python", "rust", "go", "java", "c", "cpp"]
 }
@@ -4,6 +4,21 @@ mod types;
 mod chunker;
 mod extractor;
 mod language;
+mod visibility;
+mod symbols;
+mod docs;
+mod imports;
+mod exports;
+mod calls;
+mod identifiers;
+mod testing;
+mod metrics;
+mod diagnostics;
+mod identity;
+mod recovery;
+mod resolver;
+mod symbol_table;
+mod tokenizer;
 
 use wasm_bindgen::prelude::*;
 
@@ -21,9 +36,28 @@ pub use chunker::{
     MAX_LINES_PER_CHUNK,
     MIN_LINES_PER_CHUNK,
     chunk_code,
+    chunk_code_with_mode,
+    chunk_code_with_options,
     split_large_chunk,
+    split_large_chunk_with,
+    split_large_chunk_with_overlap,
 };
 
+// Re-export cross-file resolution
+pub use resolver::{resolve_imports, ResolveOptions};
+pub use symbol_table::SymbolTable;
+
+// Re-export diagnostics limits
+pub use diagnostics::MAX_DIAGNOSTICS;
+
+// Re-export chunk identity
+pub use identity::content_hash;
+
+// Re-export tokenizers
+pub use tokenizer::{default_tokenizer, tokenizer_for, HeuristicTokenizer, Tokenizer, TOKENIZERS};
+#[cfg(feature = "bpe")]
+pub use tokenizer::BpeTokenizer;
+
 // Re-export language configuration
 pub use language::{
     get_language_config,
@@ -45,9 +79,22 @@ pub fn create_parser(language: &str) -> Result<PrismParser, JsValue> {
 }
 
 /// Parse code and extract chunks (convenience function)
+///
+/// `options` is an optional `ChunkingOptions` object.
+#[wasm_bindgen]
+pub fn parse_code(code: &str, language: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let mut parser = PrismParser::new(language)?;
+    parser.set_options(options)?;
+    let result = parser.parse(code)?;
+    serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Parse the code of a file; its path is part of every chunk id
 #[wasm_bindgen]
-pub fn parse_code(code: &str, language: &str) -> Result<JsValue, JsValue> {
+pub fn parse_file(code: &str, language: &str, file_path: &str, options: JsValue) -> Result<JsValue, JsValue> {
     let mut parser = PrismParser::new(language)?;
+    parser.set_file_path(file_path);
+    parser.set_options(options)?;
     let result = parser.parse(code)?;
     serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
 }
@@ -59,6 +106,18 @@ pub fn get_supported_languages() -> JsValue {
     serde_wasm_bindgen::to_value(&languages).unwrap_or_else(|_| JsValue::NULL)
 }
 
+/// Count the tokens of some text with the named tokenizer
+#[wasm_bindgen]
+pub fn count_tokens(text: &str, tokenizer: &str) -> Result<usize, JsValue> {
+    Ok(tokenizer_for(tokenizer)?.count_tokens(text))
+}
+
+/// Get supported tokenizers
+#[wasm_bindgen]
+pub fn get_supported_tokenizers() -> JsValue {
+    serde_wasm_bindgen::to_value(TOKENIZERS).unwrap_or(JsValue::NULL)
+}
+
 /// Get version information
 #[wasm_bindgen]
 pub fn get_version() -> String {
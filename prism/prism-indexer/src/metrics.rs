@@ -0,0 +1,274 @@
+//! Per-function complexity metrics
+//!
+//! Cyclomatic complexity counts decision points (McCabe); cognitive
+//! complexity weights control flow by how deeply it is nested, following
+//! SonarSource's definition. Both are computed from the syntax tree of the
+//! function body; named nested functions are measured on their own.
+
+use crate::extractor::function_name;
+use crate::types::{ComplexityMetrics, Parameter};
+use tree_sitter::Node;
+
+/// `if` statements and expressions
+const IF_NODES: &[&str] = &["if_statement", "if_expression", "elif_clause"];
+
+/// Loops of every grammar
+const LOOP_NODES: &[&str] = &[
+    "for_statement",
+    "for_in_statement",
+    "for_range_loop",
+    "enhanced_for_statement",
+    "while_statement",
+    "do_statement",
+    "for_expression",
+    "while_expression",
+    "loop_expression",
+];
+
+/// Multi-way branches; their cases are the decision points
+const SWITCH_NODES: &[&str] = &[
+    "switch_statement",
+    "switch_expression",
+    "match_expression",
+    "match_statement",
+    "expression_switch_statement",
+    "type_switch_statement",
+    "select_statement",
+];
+
+/// Cases of a multi-way branch
+const CASE_NODES: &[&str] = &[
+    "switch_case",
+    "case_statement",
+    "switch_block_statement_group",
+    "switch_rule",
+    "match_arm",
+    "case_clause",
+    "expression_case",
+    "type_case",
+    "communication_case",
+];
+
+/// Exception handlers
+const CATCH_NODES: &[&str] = &["catch_clause", "except_clause"];
+
+/// Conditional expressions: `a ? b : c`, `b if a else c`
+const TERNARY_NODES: &[&str] = &["ternary_expression", "conditional_expression"];
+
+/// Anonymous functions, which nest the code inside them
+const LAMBDA_NODES: &[&str] = &[
+    "arrow_function",
+    "function_expression",
+    "function",
+    "generator_function",
+    "lambda",
+    "lambda_expression",
+    "closure_expression",
+    "func_literal",
+];
+
+/// Named functions that get metrics of their own
+const FUNCTION_NODES: &[&str] = &[
+    "function_declaration",
+    "function_definition",
+    "method_definition",
+    "function_item",
+    "generator_function_declaration",
+    "method_declaration",
+    "constructor_declaration",
+];
+
+/// Declarations counted as statements
+const DECLARATION_STATEMENTS: &[&str] = &[
+    "lexical_declaration",
+    "variable_declaration",
+    "local_variable_declaration",
+    "let_declaration",
+    "short_var_declaration",
+    "var_declaration",
+    "const_declaration",
+    "declaration",
+];
+
+/// Receivers that are not parameters in the caller's sense
+const RECEIVER_PARAMETERS: &[&str] = &["self", "&self", "&mut self", "mut self", "this"];
+
+/// Measure a function; `declaration` spans the lines attributed to it
+pub(crate) fn function_metrics(node: &Node, declaration: &Node, parameters: &[Parameter], source: &str) -> ComplexityMetrics {
+    let mut metrics = ComplexityMetrics {
+        cyclomatic: 1,
+        parameters: parameters
+            .iter()
+            .filter(|p| !RECEIVER_PARAMETERS.contains(&p.name.as_str()))
+            .count(),
+        lines_of_code: lines_of_code(declaration),
+        ..ComplexityMetrics::default()
+    };
+
+    if let Some(body) = node.child_by_field_name("body") {
+        // Expression-bodied arrows and lambdas: `x => x ? a : b`
+        visit_node(&body, source, 0, &mut metrics);
+    }
+    metrics
+}
+
+/// Score the children of a node at the given nesting level
+fn visit(node: &Node, source: &str, nesting: usize, metrics: &mut ComplexityMetrics) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        visit_node(&child, source, nesting, metrics);
+    }
+}
+
+/// Score one node and its subtree
+fn visit_node(node: &Node, source: &str, nesting: usize, metrics: &mut ComplexityMetrics) {
+    let kind = node.kind();
+    if FUNCTION_NODES.contains(&kind) && function_name(node, source).is_some() {
+        return;
+    }
+
+    if is_statement(node) {
+        metrics.statements += 1;
+    }
+
+    if IF_NODES.contains(&kind) {
+        structure(nesting, metrics);
+        visit_if(node, source, nesting, metrics);
+    } else if LOOP_NODES.contains(&kind) || CATCH_NODES.contains(&kind) || TERNARY_NODES.contains(&kind) {
+        structure(nesting, metrics);
+        visit(node, source, nesting + 1, metrics);
+    } else if SWITCH_NODES.contains(&kind) {
+        // The whole switch is one structure; each case is a path
+        metrics.cognitive += 1 + nesting;
+        metrics.max_nesting = metrics.max_nesting.max(nesting + 1);
+        visit(node, source, nesting + 1, metrics);
+    } else if CASE_NODES.contains(&kind) {
+        if !is_default_case(node, source) {
+            metrics.cyclomatic += 1;
+        }
+        visit(node, source, nesting, metrics);
+    } else if LAMBDA_NODES.contains(&kind) {
+        visit(node, source, nesting + 1, metrics);
+    } else {
+        if let Some(operator) = logical_operator(node, source) {
+            metrics.cyclomatic += 1;
+            // `a && b && c` is one sequence, `a && b || c` two
+            let continues_sequence = node
+                .parent()
+                .is_some_and(|parent| logical_operator(&parent, source) == Some(operator));
+            if !continues_sequence {
+                metrics.cognitive += 1;
+            }
+        }
+        if is_labeled_jump(node) {
+            metrics.cognitive += 1;
+        }
+        visit(node, source, nesting, metrics);
+    }
+}
+
+/// Score an `if`: its condition and consequence are nested, while `else if`
+/// and `else` continue at the level of the `if` itself
+fn visit_if(node: &Node, source: &str, nesting: usize, metrics: &mut ComplexityMetrics) {
+    let mut cursor = node.walk();
+    for (index, child) in node.named_children(&mut cursor).enumerate() {
+        let is_alternative = node.field_name_for_named_child(index as u32) == Some("alternative");
+        if !is_alternative {
+            visit_node(&child, source, nesting + 1, metrics);
+            continue;
+        }
+
+        // `else_clause` wraps the alternative in most grammars
+        let inner = if child.kind() == "else_clause" {
+            let mut else_cursor = child.walk();
+            let inner = child
+                .named_children(&mut else_cursor)
+                .find(|n| !n.kind().contains("comment"))
+                .unwrap_or(child);
+            inner
+        } else {
+            child
+        };
+
+        if IF_NODES.contains(&inner.kind()) {
+            // `else if`: a decision, but not a deeper one
+            metrics.cyclomatic += 1;
+            metrics.cognitive += 1;
+            if is_statement(&inner) {
+                metrics.statements += 1;
+            }
+            visit_if(&inner, source, nesting, metrics);
+        } else {
+            metrics.cognitive += 1;
+            visit_node(&inner, source, nesting + 1, metrics);
+        }
+    }
+}
+
+/// Count a nesting control structure at the given level
+fn structure(nesting: usize, metrics: &mut ComplexityMetrics) {
+    metrics.cyclomatic += 1;
+    metrics.cognitive += 1 + nesting;
+    metrics.max_nesting = metrics.max_nesting.max(nesting + 1);
+}
+
+/// Whether a node is a statement of the function body
+fn is_statement(node: &Node) -> bool {
+    let kind = node.kind();
+    (kind.ends_with("_statement") && !matches!(kind, "compound_statement" | "empty_statement" | "labeled_statement"))
+        || DECLARATION_STATEMENTS.contains(&kind)
+}
+
+/// `&&`/`||` of C-like languages, `and`/`or` of Python
+fn logical_operator<'a>(node: &Node, source: &'a str) -> Option<&'a str> {
+    if !matches!(node.kind(), "binary_expression" | "boolean_operator") {
+        return None;
+    }
+    let operator = node.child_by_field_name("operator")?;
+    let text = &source[operator.byte_range()];
+    matches!(text, "&&" | "||" | "and" | "or").then_some(text)
+}
+
+/// `break outer`, `continue 'outer`, `goto fail`
+fn is_labeled_jump(node: &Node) -> bool {
+    let mut cursor = node.walk();
+    let mut children = node.named_children(&mut cursor);
+    match node.kind() {
+        "goto_statement" => true,
+        "break_statement" | "continue_statement" => {
+            children.any(|child| matches!(child.kind(), "statement_identifier" | "label_name" | "identifier"))
+        }
+        // Rust `break 'outer value`
+        "break_expression" | "continue_expression" => children.any(|child| child.kind() == "label"),
+        _ => false,
+    }
+}
+
+/// `default:`, `default ->`, or a catch-all `_` pattern
+fn is_default_case(node: &Node, source: &str) -> bool {
+    let text = source[node.byte_range()].trim_start();
+    text.starts_with("default") || node.named_child(0).is_some_and(|pattern| source[pattern.byte_range()].trim() == "_")
+}
+
+/// Lines holding code, not counting blank and comment-only lines
+fn lines_of_code(node: &Node) -> usize {
+    let mut lines = Vec::new();
+    collect_code_lines(node, &mut lines);
+    lines.sort_unstable();
+    lines.dedup();
+    lines.len()
+}
+
+fn collect_code_lines(node: &Node, lines: &mut Vec<usize>) {
+    if node.kind().contains("comment") {
+        return;
+    }
+    if node.child_count() == 0 {
+        lines.extend(node.start_position().row..=node.end_position().row);
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_code_lines(&child, lines);
+    }
+}
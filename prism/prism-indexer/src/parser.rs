@@ -1,5 +1,8 @@
+use crate::chunker::ChunkSettings;
 use crate::error::{PrismError, Result};
-use crate::types::{ParseResult, CodeChunk};
+use crate::extractor::FileSyntax;
+use crate::tokenizer::{default_tokenizer, tokenizer_for, Tokenizer};
+use crate::types::{ChunkingOptions, NestedSymbols, ParseResult, CodeChunk};
 use tree_sitter::Parser;
 use wasm_bindgen::prelude::*;
 
@@ -8,6 +11,9 @@ use wasm_bindgen::prelude::*;
 pub struct PrismParser {
     parser: Parser,
     language_name: String,
+    settings: ChunkSettings,
+    file_path: String,
+    tokenizer: Box<dyn Tokenizer>,
 }
 
 #[wasm_bindgen]
@@ -19,24 +25,80 @@ impl PrismParser {
 
         let language_obj = match language {
             "typescript" => tree_sitter_typescript::language_typescript(),
+            "tsx" | "jsx" => tree_sitter_typescript::language_tsx(),
             "javascript" => tree_sitter_javascript::language_javascript(),
             "python" => tree_sitter_python::language_python(),
             "rust" => tree_sitter_rust::language_rust(),
             "go" => tree_sitter_go::language_go(),
             "java" => tree_sitter_java::language_java(),
+            "c" => tree_sitter_c::LANGUAGE.into(),
+            "cpp" | "c++" => tree_sitter_cpp::LANGUAGE.into(),
             _ => return Err(PrismError::UnsupportedLanguage(language.to_string())),
         };
 
         parser
             .set_language(&language_obj)
             .map_err(|e| PrismError::ParseError(format!("Failed to set language: {:?}", e)))?;
+        let settings = ChunkSettings::defaults(language);
 
         Ok(PrismParser {
             parser,
             language_name: language.to_string(),
+            settings,
+            file_path: String::new(),
+            tokenizer: default_tokenizer(),
         })
     }
 
+    /// Give nested functions and classes their own chunks
+    #[wasm_bindgen]
+    pub fn set_include_nested(&mut self, include: bool) {
+        self.settings.nested = if include {
+            NestedSymbols::Include
+        } else {
+            NestedSymbols::Exclude
+        };
+    }
+
+    /// Set chunking options from a JS object, such as `{ max_tokens: 800 }`
+    #[wasm_bindgen]
+    pub fn set_options(&mut self, options: JsValue) -> Result<()> {
+        let options = if options.is_undefined() || options.is_null() {
+            ChunkingOptions::default()
+        } else {
+            serde_wasm_bindgen::from_value(options).map_err(|e| PrismError::InvalidOptions(e.to_string()))?
+        };
+        self.set_chunking_options(&options)
+    }
+
+    /// Set the path of the file being parsed, which chunk ids are derived from
+    #[wasm_bindgen]
+    pub fn set_file_path(&mut self, path: &str) {
+        self.file_path = path.to_string();
+    }
+
+    /// Count chunk tokens with the named tokenizer: `cl100k` or `heuristic`
+    #[wasm_bindgen]
+    pub fn set_tokenizer(&mut self, name: &str) -> Result<()> {
+        self.tokenizer = tokenizer_for(name)?;
+        Ok(())
+    }
+
+    /// Count chunk tokens with a vocabulary in tiktoken format, such as the
+    /// exact vocabulary of a local model
+    #[cfg(feature = "bpe")]
+    #[wasm_bindgen]
+    pub fn set_tokenizer_vocabulary(&mut self, name: &str, ranks: &str) -> Result<()> {
+        self.tokenizer = Box::new(crate::tokenizer::BpeTokenizer::from_tiktoken(name, ranks)?);
+        Ok(())
+    }
+
+    /// Name of the tokenizer chunk tokens are counted with
+    #[wasm_bindgen(getter)]
+    pub fn tokenizer(&self) -> String {
+        self.tokenizer.name().to_string()
+    }
+
     /// Parse code and return structured result
     #[wasm_bindgen]
     pub fn parse(&mut self, code: &str) -> Result<ParseResult> {
@@ -48,16 +110,31 @@ impl PrismParser {
         let root = tree.root_node();
         let has_errors = root.has_error();
 
-        // Extract code chunks (now with function-level chunking)
-        let chunks = crate::chunker::chunk_code(&root, code, &self.language_name);
+        // Extract functions, classes and symbols once for chunks and metadata
+        let syntax = FileSyntax::extract(&root, code, &self.language_name);
 
-        // Extract functions and classes for metadata
-        let functions = crate::extractor::extract_functions(&root, code);
-        let classes = crate::extractor::extract_classes(&root, code);
+        // Extract code chunks (now with function-level chunking)
+        let chunks = crate::chunker::chunk_file(
+            &root,
+            code,
+            &self.language_name,
+            &self.file_path,
+            &self.settings,
+            self.tokenizer.as_ref(),
+            &syntax,
+        );
+        let FileSyntax {
+            functions,
+            classes,
+            symbols,
+            imports,
+            ..
+        } = syntax;
+        let exports = crate::exports::extract_exports(&root, code, &self.language_name, &imports);
 
         // Find error nodes if any
         let error_nodes = if has_errors {
-            crate::extractor::find_error_nodes(&root, code)
+            crate::diagnostics::find_error_nodes(&root, code)
         } else {
             Vec::new()
         };
@@ -68,6 +145,9 @@ impl PrismParser {
             chunks,
             functions,
             classes,
+            imports,
+            exports,
+            symbols,
         })
     }
 
@@ -78,3 +158,11 @@ impl PrismParser {
         // This is a placeholder for future cleanup needs
     }
 }
+
+impl PrismParser {
+    /// Set how code is split into chunks; unset options take the language defaults
+    pub fn set_chunking_options(&mut self, options: &ChunkingOptions) -> Result<()> {
+        self.settings = ChunkSettings::resolve(options, &self.language_name)?;
+        Ok(())
+    }
+}
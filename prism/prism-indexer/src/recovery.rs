@@ -0,0 +1,307 @@
+//! Error-tolerant chunking
+//!
+//! Half-edited files parse with ERROR nodes that can swallow whole
+//! declarations. Definitions the parser still recognised are kept, but cut
+//! short where they run into code at their own indentation; the lines the
+//! parser lost are split into declaration-sized blocks by indentation and
+//! brace heuristics.
+
+use crate::types::SymbolKind;
+use tree_sitter::Node;
+
+/// Words that may precede a declaration keyword
+const MODIFIERS: &[&str] = &[
+    "export", "default", "declare", "pub", "async", "unsafe", "extern", "public", "private", "protected",
+    "internal", "static", "abstract", "final", "sealed", "inline", "virtual", "override", "synchronized",
+];
+
+/// Keywords that open a declaration, and the kind they declare
+const DECLARATION_KEYWORDS: &[(&str, Option<SymbolKind>)] = &[
+    ("function", Some(SymbolKind::Function)),
+    ("function*", Some(SymbolKind::Function)),
+    ("def", Some(SymbolKind::Function)),
+    ("fn", Some(SymbolKind::Function)),
+    ("func", Some(SymbolKind::Function)),
+    ("class", Some(SymbolKind::Class)),
+    ("record", Some(SymbolKind::Class)),
+    ("interface", Some(SymbolKind::Interface)),
+    ("struct", Some(SymbolKind::Struct)),
+    ("union", Some(SymbolKind::Struct)),
+    ("enum", Some(SymbolKind::Enum)),
+    ("trait", Some(SymbolKind::Trait)),
+    ("type", Some(SymbolKind::TypeAlias)),
+    ("mod", Some(SymbolKind::Module)),
+    ("namespace", Some(SymbolKind::Module)),
+    ("module", Some(SymbolKind::Module)),
+    ("impl", None),
+];
+
+/// Statements that look like calls but never start a declaration
+const CONTROL_KEYWORDS: &[&str] = &[
+    "if", "else", "for", "while", "do", "switch", "case", "return", "catch", "try", "throw", "new", "delete",
+    "sizeof", "await", "yield",
+];
+
+/// A run of lines the parser lost, recovered as one declaration
+#[derive(Debug)]
+pub(crate) struct RecoveredBlock {
+    /// 1-based, inclusive
+    pub(crate) start_line: usize,
+    pub(crate) end_line: usize,
+    pub(crate) kind: Option<SymbolKind>,
+}
+
+/// A line that opens a declaration
+struct Header {
+    kind: Option<SymbolKind>,
+    /// Python decorators open the declaration on a following line
+    is_decorator: bool,
+}
+
+/// Syntax errors of a file and the source lines around them
+pub(crate) struct Recovery<'a> {
+    lines: Vec<&'a str>,
+    language: &'a str,
+    /// 1-based, inclusive lines of the outermost ERROR and MISSING nodes
+    error_ranges: Vec<(usize, usize)>,
+}
+
+impl<'a> Recovery<'a> {
+    /// Locate the syntax errors of a file; `None` when it parsed cleanly
+    pub(crate) fn new(root: &Node, source: &'a str, language: &'a str) -> Option<Self> {
+        if !root.has_error() {
+            return None;
+        }
+        let mut error_ranges = Vec::new();
+        collect_error_ranges(root, &mut error_ranges);
+
+        Some(Recovery {
+            lines: source.lines().collect(),
+            language,
+            error_ranges,
+        })
+    }
+
+    /// Whether any syntax error touches the given lines
+    pub(crate) fn has_errors(&self, start_line: usize, end_line: usize) -> bool {
+        self.error_ranges
+            .iter()
+            .any(|&(start, end)| start <= end_line && end >= start_line)
+    }
+
+    /// Cut a definition short where it runs into a declaration at its own
+    /// indentation, which the parser swallowed into it
+    pub(crate) fn trim(&self, span: (usize, usize)) -> (usize, usize) {
+        let (start_line, end_line) = span;
+        if !self.has_errors(start_line, end_line) {
+            return span;
+        }
+
+        let base = indentation(self.line(start_line));
+        let swallowed = (start_line + 1..=end_line).find(|&line| {
+            self.header(line).is_some()
+                && indentation(self.line(line)) <= base
+                && !self.continues_decorators(start_line, line)
+        });
+        match swallowed {
+            Some(line) => (start_line, self.last_code_line(start_line, line - 1)),
+            None => span,
+        }
+    }
+
+    /// Declarations recovered from the uncovered lines of error regions
+    pub(crate) fn blocks(&self, covered_lines: &[bool]) -> Vec<RecoveredBlock> {
+        let mut blocks = Vec::new();
+
+        let mut line = 1;
+        while line <= covered_lines.len() {
+            if covered_lines[line - 1] {
+                line += 1;
+                continue;
+            }
+            let section_start = line;
+            while line <= covered_lines.len() && !covered_lines[line - 1] {
+                line += 1;
+            }
+            let section_end = line - 1;
+
+            if self.has_errors(section_start, section_end) {
+                self.split_section(section_start, section_end, &mut blocks);
+            }
+        }
+
+        blocks
+    }
+
+    /// Split uncovered lines at the declarations of their outermost level
+    fn split_section(&self, start_line: usize, end_line: usize, blocks: &mut Vec<RecoveredBlock>) {
+        let headers: Vec<usize> = (start_line..=end_line).filter(|&line| self.header(line).is_some()).collect();
+        let Some(level) = headers.iter().map(|&line| indentation(self.line(line))).min() else {
+            return;
+        };
+
+        // A decorator and the declaration below it open a single block
+        let mut starts: Vec<usize> = Vec::new();
+        for &line in headers.iter().filter(|&&line| indentation(self.line(line)) == level) {
+            if !starts.last().is_some_and(|&start| self.continues_decorators(start, line)) {
+                starts.push(line);
+            }
+        }
+
+        for (index, &start) in starts.iter().enumerate() {
+            let limit = starts.get(index + 1).map_or(end_line, |&next| next - 1);
+            let end = self.block_end(start, limit);
+            let kind = (start..=end)
+                .filter_map(|line| self.header(line))
+                .find(|header| !header.is_decorator)
+                .and_then(|header| header.kind);
+            blocks.push(RecoveredBlock {
+                start_line: start,
+                end_line: end,
+                kind,
+            });
+        }
+    }
+
+    /// Last line of a declaration starting at `start_line`, at most `limit`
+    fn block_end(&self, start_line: usize, limit: usize) -> usize {
+        if self.language == "python" {
+            // The body is everything indented deeper than the header
+            let base = indentation(self.line(start_line));
+            let end = (start_line + 1..=limit)
+                .find(|&line| {
+                    let text = self.line(line);
+                    !text.trim().is_empty()
+                        && indentation(text) <= base
+                        && !self.continues_decorators(start_line, line)
+                })
+                .map_or(limit, |line| line - 1);
+            return self.last_code_line(start_line, end);
+        }
+
+        // Brace languages: the block closes when its braces balance
+        let mut depth = 0i32;
+        let mut opened = false;
+        for line in start_line..=limit {
+            for delta in brace_deltas(self.line(line), self.language) {
+                depth += delta;
+                opened |= depth > 0;
+                if opened && depth <= 0 {
+                    return line;
+                }
+            }
+        }
+        self.last_code_line(start_line, limit)
+    }
+
+    /// Whether `line` is the declaration of decorators that start at `start_line`
+    fn continues_decorators(&self, start_line: usize, line: usize) -> bool {
+        (start_line..line).all(|l| {
+            self.line(l).trim().is_empty() || self.header(l).is_some_and(|header| header.is_decorator)
+        })
+    }
+
+    /// The last non-blank line in a range, or its start
+    fn last_code_line(&self, start_line: usize, end_line: usize) -> usize {
+        (start_line..=end_line)
+            .rev()
+            .find(|&line| !self.line(line).trim().is_empty())
+            .unwrap_or(start_line)
+    }
+
+    fn line(&self, line: usize) -> &str {
+        self.lines.get(line - 1).copied().unwrap_or("")
+    }
+
+    /// Whether a line opens a declaration, judging by its text alone
+    fn header(&self, line: usize) -> Option<Header> {
+        let text = self.line(line).trim();
+        if text.starts_with('@') && self.language == "python" {
+            return Some(Header { kind: None, is_decorator: true });
+        }
+
+        let mut words = text
+            .split_whitespace()
+            .skip_while(|word| MODIFIERS.contains(word) || word.starts_with("pub("));
+        let keyword = words.next()?;
+
+        if let Some(&(_, kind)) = DECLARATION_KEYWORDS.iter().find(|(k, _)| *k == keyword) {
+            // `type` and `module` are also common identifiers
+            let names_something = words
+                .next()
+                .is_some_and(|name| name.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '('));
+            return names_something.then_some(Header { kind, is_decorator: false });
+        }
+
+        // `const handler = async (req) => {`, `var f = function () {`
+        if matches!(keyword, "const" | "let" | "var") && (text.contains("=>") || text.contains("= function")) {
+            return Some(Header { kind: Some(SymbolKind::Function), is_decorator: false });
+        }
+
+        // C, C++ and Java functions and methods: `static int parse(const char *s) {`
+        if matches!(self.language, "c" | "cpp" | "java") {
+            let before_paren = text.split('(').next()?;
+            let is_signature = text.contains('(')
+                && !text.ends_with(';')
+                && !before_paren.contains('=')
+                && !CONTROL_KEYWORDS.contains(&keyword)
+                && (before_paren.split_whitespace().count() >= 2 || before_paren.contains("::"))
+                && (text.ends_with('{') || text.ends_with(')'));
+            return is_signature.then_some(Header { kind: Some(SymbolKind::Function), is_decorator: false });
+        }
+
+        None
+    }
+}
+
+/// Record the lines of the outermost error nodes
+fn collect_error_ranges(node: &Node, ranges: &mut Vec<(usize, usize)>) {
+    if !node.has_error() {
+        return;
+    }
+    if node.is_error() || node.is_missing() {
+        let start = node.start_position();
+        let end = node.end_position();
+        // An error ending at column 0 stops before that line
+        let end_line = if end.column == 0 && end.row > start.row { end.row } else { end.row + 1 };
+        ranges.push((start.row + 1, end_line));
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_error_ranges(&child, ranges);
+    }
+}
+
+/// Number of leading whitespace columns
+fn indentation(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// `+1`/`-1` for every brace outside of strings and line comments
+fn brace_deltas(line: &str, language: &str) -> Vec<i32> {
+    let mut deltas = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(_) if c == '\\' => {
+                chars.next();
+            }
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            // Rust lifetimes (`'a`) are not character literals
+            None if c == '"' || c == '`' || (c == '\'' && language != "rust") => quote = Some(c),
+            None if c == '/' && chars.peek() == Some(&'/') => break,
+            None if c == '#' && language == "python" => break,
+            None if c == '{' => deltas.push(1),
+            None if c == '}' => deltas.push(-1),
+            None => {}
+        }
+    }
+    deltas
+}
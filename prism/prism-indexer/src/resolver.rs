@@ -0,0 +1,419 @@
+//! Cross-file import resolution
+//!
+//! Maps the module specifiers of a batch of parsed files onto the files of
+//! the same project. Resolution works on the path list alone, so it runs
+//! unchanged in WASM: relative TS/JS specifiers with extension and index
+//! probing, Python modules and packages, Rust `crate::`/`self::`/`super::`
+//! module paths, Go module-relative packages, Java packages and C/C++
+//! includes.
+
+use crate::types::{BindingKind, ImportGraph, ImportInfo, ParseResult, ResolvedImport, UnresolvedImport};
+use std::collections::{HashMap, HashSet};
+
+/// Extensions probed for extensionless TS/JS specifiers, in order
+const JS_EXTENSIONS: &[&str] = &[".ts", ".tsx", ".d.ts", ".js", ".jsx", ".mjs", ".cjs"];
+
+/// Project settings that can't be derived from the file list
+#[derive(Debug, Clone, Default)]
+pub struct ResolveOptions {
+    /// Go module path declared in `go.mod` (`github.com/acme/app`); imports
+    /// under it resolve to package directories of the project
+    pub go_module: Option<String>,
+}
+
+/// Outcome of resolving a single import
+enum Resolution {
+    Files(Vec<String>),
+    /// Looks local (relative path, `crate::`, Go module path) but no file matches
+    Unresolved,
+    /// Standard library or third-party package
+    External,
+}
+
+/// Resolve the imports of every file to the files they refer to
+///
+/// Paths are project-relative and use `/` separators. Imports that resolve
+/// become edges; the rest are reported as unresolved or external. An import
+/// of a file's own items (`use crate::Config` in `lib.rs`) is a self-edge.
+pub fn resolve_imports(files: &[(&str, &ParseResult)], options: &ResolveOptions) -> ImportGraph {
+    let project = Project::new(files);
+    let mut graph = ImportGraph::default();
+
+    for (path, result) in files {
+        let from = normalize(path);
+        for import in &result.imports {
+            match project.resolve(&from, import, options) {
+                Resolution::Files(mut targets) => {
+                    if targets.len() > 1 {
+                        targets.retain(|to| *to != from);
+                    }
+                    graph.edges.extend(targets.into_iter().map(|to| ResolvedImport {
+                        from: from.clone(),
+                        to,
+                        source: import.source.clone(),
+                        is_type_only: import.is_type_only,
+                    }));
+                }
+                Resolution::Unresolved => graph.unresolved.push(unresolved(&from, import)),
+                Resolution::External => graph.external.push(unresolved(&from, import)),
+            }
+        }
+    }
+
+    graph
+}
+
+/// The files of the project being resolved against
+pub(crate) struct Project {
+    files: HashSet<String>,
+    /// Shortest paths first, so suffix lookups prefer the least nested match
+    ordered: Vec<String>,
+    /// Top-level and exported names of each file
+    declarations: HashMap<String, HashSet<String>>,
+}
+
+impl Project {
+    pub(crate) fn new(files: &[(&str, &ParseResult)]) -> Self {
+        let mut ordered: Vec<String> = files.iter().map(|(path, _)| normalize(path)).collect();
+        ordered.sort_by(|a, b| a.matches('/').count().cmp(&b.matches('/').count()).then(a.cmp(b)));
+        ordered.dedup();
+
+        let declarations = files
+            .iter()
+            .map(|(path, result)| {
+                let names = result
+                    .symbols
+                    .iter()
+                    .map(|symbol| symbol.name.clone())
+                    .chain(result.exports.iter().map(|export| export.name.clone()))
+                    .collect();
+                (normalize(path), names)
+            })
+            .collect();
+
+        Project {
+            files: ordered.iter().cloned().collect(),
+            ordered,
+            declarations,
+        }
+    }
+
+    /// Files an import refers to; empty when it doesn't resolve
+    pub(crate) fn targets(&self, from: &str, import: &ImportInfo, options: &ResolveOptions) -> Vec<String> {
+        match self.resolve(from, import, options) {
+            Resolution::Files(files) => files,
+            Resolution::Unresolved | Resolution::External => Vec::new(),
+        }
+    }
+
+    fn resolve(&self, from: &str, import: &ImportInfo, options: &ResolveOptions) -> Resolution {
+        match extension(from) {
+            "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" | "mts" | "cts" => self.resolve_js(from, import),
+            "py" | "pyi" => self.resolve_python(from, import),
+            "rs" => self.resolve_rust(from, import),
+            "go" => self.resolve_go(import, options),
+            "java" => self.resolve_java(import),
+            "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => self.resolve_include(from, import),
+            _ => Resolution::External,
+        }
+    }
+
+    /// `./utils` → `utils.ts`, `utils/index.ts`; `./a.js` may name `a.ts`
+    fn resolve_js(&self, from: &str, import: &ImportInfo) -> Resolution {
+        let specifier = &import.source;
+        if !specifier.starts_with('.') && !specifier.starts_with('/') {
+            return Resolution::External;
+        }
+
+        let base = normalize(&join(parent_dir(from), specifier));
+        let mut candidates = vec![base.clone()];
+        candidates.extend(JS_EXTENSIONS.iter().map(|ext| format!("{}{}", base, ext)));
+        candidates.extend(JS_EXTENSIONS.iter().map(|ext| format!("{}/index{}", base, ext)));
+        // TS ESM imports name the emitted file: `import './a.js'` for `a.ts`
+        if let Some(stem) = base.strip_suffix(".js").or_else(|| base.strip_suffix(".jsx")) {
+            candidates.extend([".ts", ".tsx"].iter().map(|ext| format!("{}{}", stem, ext)));
+        }
+
+        self.first_file(&candidates).map_or(Resolution::Unresolved, |file| Resolution::Files(vec![file]))
+    }
+
+    /// `from ..models import user` → `../models/user.py`, `../models/__init__.py`;
+    /// absolute imports match any source root
+    fn resolve_python(&self, from: &str, import: &ImportInfo) -> Resolution {
+        let module = import.source.trim_start_matches('.').replace('.', "/");
+        let mut modules: Vec<String> = named_bindings(import)
+            .map(|name| join(&module, name))
+            .collect();
+        modules.push(module);
+
+        let candidates = |module: &str| -> Vec<String> {
+            if module.is_empty() {
+                vec!["__init__.py".to_string()]
+            } else {
+                vec![format!("{}.py", module), format!("{}.pyi", module), format!("{}/__init__.py", module)]
+            }
+        };
+
+        if import.relative_level == 0 {
+            let found = modules
+                .iter()
+                .filter(|m| !m.is_empty())
+                .find_map(|m| candidates(m).iter().find_map(|c| self.find_by_suffix(c)));
+            return found.map_or(Resolution::External, |file| Resolution::Files(vec![file]));
+        }
+
+        // `.` is the importing file's package, each further dot one level up
+        let mut base = parent_dir(from).to_string();
+        for _ in 1..import.relative_level {
+            base = parent_dir(&base).to_string();
+        }
+        let found = modules.iter().find_map(|m| {
+            let paths: Vec<String> = candidates(m).iter().map(|c| normalize(&join(&base, c))).collect();
+            self.first_file(&paths)
+        });
+        found.map_or(Resolution::Unresolved, |file| Resolution::Files(vec![file]))
+    }
+
+    /// `crate::a::b::C` → `a/b.rs` or `a/b/mod.rs` under the crate root, or
+    /// the root itself when it declares `a`; every other path names an
+    /// external crate
+    fn resolve_rust(&self, from: &str, import: &ImportInfo) -> Resolution {
+        let mut segments = import.source.split("::").filter(|s| !s.is_empty());
+        let Some(root) = self.crate_root(from) else {
+            return Resolution::External;
+        };
+
+        let mut module = match segments.next() {
+            Some("crate") => Vec::new(),
+            Some("self") => self.module_path(&root, from),
+            Some("super") => {
+                let mut module = self.module_path(&root, from);
+                module.pop();
+                module
+            }
+            _ => return Resolution::External,
+        };
+        for segment in segments {
+            if segment == "super" {
+                module.pop();
+            } else {
+                module.push(segment.to_string());
+            }
+        }
+
+        // Imported names may be modules themselves: `use crate::{parser, types::*}`
+        let mut paths: Vec<Vec<String>> = named_bindings(import)
+            .map(|name| {
+                let mut path = module.clone();
+                path.push(name.to_string());
+                path
+            })
+            .collect();
+        // The module itself, unless it is the crate root of `use crate::{a, b}`
+        if paths.is_empty() || !module.is_empty() {
+            paths.push(module);
+        }
+
+        let mut targets = Vec::new();
+        for path in paths {
+            // The longest prefix that is a module file; the rest names items
+            let file = match path.first() {
+                None => self.module_file(&root, &[]),
+                Some(name) => (1..=path.len())
+                    .rev()
+                    .find_map(|n| self.module_file(&root, &path[..n]))
+                    .or_else(|| self.module_file(&root, &[]).filter(|file| self.declares(file, name))),
+            };
+            if let Some(file) = file.filter(|file| !targets.contains(file)) {
+                targets.push(file);
+            }
+        }
+
+        if targets.is_empty() {
+            Resolution::Unresolved
+        } else {
+            Resolution::Files(targets)
+        }
+    }
+
+    /// Nearest directory above `from` holding `lib.rs` or `main.rs`
+    fn crate_root(&self, from: &str) -> Option<String> {
+        let mut dir = parent_dir(from);
+        loop {
+            if ["lib.rs", "main.rs"].iter().any(|root| self.files.contains(&join(dir, root))) {
+                return Some(dir.to_string());
+            }
+            if dir.is_empty() {
+                return None;
+            }
+            dir = parent_dir(dir);
+        }
+    }
+
+    /// Module path of a file: `src/a/b.rs` and `src/a/b/mod.rs` are `a::b`
+    fn module_path(&self, root: &str, file: &str) -> Vec<String> {
+        let relative = match root {
+            "" => file,
+            root => file.strip_prefix(root).unwrap_or(file).trim_start_matches('/'),
+        };
+        let relative = relative.strip_suffix(".rs").unwrap_or(relative);
+        let relative = relative.strip_suffix("/mod").unwrap_or(relative);
+        if relative == "lib" || relative == "main" || relative == "mod" {
+            return Vec::new();
+        }
+        relative.split('/').map(str::to_string).collect()
+    }
+
+    /// File defining a module path, if it is part of the project
+    fn module_file(&self, root: &str, module: &[String]) -> Option<String> {
+        if module.is_empty() {
+            return self.first_file(&[join(root, "lib.rs"), join(root, "main.rs")]);
+        }
+        let path = join(root, &module.join("/"));
+        self.first_file(&[format!("{}.rs", path), format!("{}/mod.rs", path)])
+    }
+
+    /// Whether a file declares or exports a top-level name
+    fn declares(&self, file: &str, name: &str) -> bool {
+        self.declarations.get(file).is_some_and(|names| names.contains(name))
+    }
+
+    /// `github.com/acme/app/pkg/util` → every non-test `.go` file in `pkg/util`
+    fn resolve_go(&self, import: &ImportInfo, options: &ResolveOptions) -> Resolution {
+        let Some(module) = options.go_module.as_deref() else {
+            return Resolution::External;
+        };
+        let package = match import.source.strip_prefix(module) {
+            Some("") => "",
+            Some(rest) if rest.starts_with('/') => rest.trim_start_matches('/'),
+            _ => return Resolution::External,
+        };
+
+        let files: Vec<String> = self
+            .ordered
+            .iter()
+            .filter(|file| file.ends_with(".go") && !file.ends_with("_test.go"))
+            .filter(|file| {
+                let dir = parent_dir(file);
+                dir == package || (!package.is_empty() && dir.ends_with(&format!("/{}", package)))
+            })
+            .cloned()
+            .collect();
+
+        if files.is_empty() {
+            Resolution::Unresolved
+        } else {
+            Resolution::Files(files)
+        }
+    }
+
+    /// `com.acme.util` + `Helper` → `com/acme/util/Helper.java`; wildcard
+    /// imports resolve to every class of the package
+    fn resolve_java(&self, import: &ImportInfo) -> Resolution {
+        let package = import.source.replace('.', "/");
+
+        if import.bindings.iter().any(|b| b.kind == BindingKind::Wildcard) {
+            let suffix = format!("{}/", package);
+            let files: Vec<String> = self
+                .ordered
+                .iter()
+                .filter(|file| file.ends_with(".java"))
+                .filter(|file| {
+                    let dir = format!("{}/", parent_dir(file));
+                    dir == suffix || dir.ends_with(&format!("/{}", suffix))
+                })
+                .cloned()
+                .collect();
+            return if files.is_empty() { Resolution::External } else { Resolution::Files(files) };
+        }
+
+        // `import static a.b.C.m` names a member of class `C`
+        let found = named_bindings(import)
+            .map(|name| format!("{}/{}.java", package, name))
+            .chain(std::iter::once(format!("{}.java", package)))
+            .find_map(|candidate| self.find_by_suffix(&candidate));
+        found.map_or(Resolution::External, |file| Resolution::Files(vec![file]))
+    }
+
+    /// `#include "util.h"` next to the including file, then anywhere
+    fn resolve_include(&self, from: &str, import: &ImportInfo) -> Resolution {
+        let local = normalize(&join(parent_dir(from), &import.source));
+        let found = self.first_file(&[local]).or_else(|| self.find_by_suffix(&import.source));
+        found.map_or(Resolution::External, |file| Resolution::Files(vec![file]))
+    }
+
+    /// First candidate that is a project file
+    fn first_file(&self, candidates: &[String]) -> Option<String> {
+        candidates.iter().find(|c| self.files.contains(*c)).cloned()
+    }
+
+    /// Least nested file whose path ends with `suffix` at a path boundary
+    fn find_by_suffix(&self, suffix: &str) -> Option<String> {
+        let bounded = format!("/{}", suffix);
+        self.ordered
+            .iter()
+            .find(|file| *file == suffix || file.ends_with(&bounded))
+            .cloned()
+    }
+}
+
+/// Imported names of named bindings
+fn named_bindings(import: &ImportInfo) -> impl Iterator<Item = &str> {
+    import
+        .bindings
+        .iter()
+        .filter(|b| b.kind == BindingKind::Named)
+        .map(|b| b.imported.as_str())
+}
+
+fn unresolved(from: &str, import: &ImportInfo) -> UnresolvedImport {
+    UnresolvedImport {
+        from: from.to_string(),
+        source: import.source.clone(),
+        location: import.location.clone(),
+    }
+}
+
+/// Extension of a path without the dot
+pub(crate) fn extension(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.rsplit_once('.').map_or("", |(_, ext)| ext)
+}
+
+/// Directory part of a path (empty at the project root)
+pub(crate) fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+/// Join a relative path onto a directory
+fn join(dir: &str, path: &str) -> String {
+    if dir.is_empty() || path.starts_with('/') {
+        path.to_string()
+    } else if path.is_empty() {
+        dir.to_string()
+    } else {
+        format!("{}/{}", dir, path)
+    }
+}
+
+/// Resolve `.` and `..` segments and normalise separators
+pub(crate) fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    let normalized = segments.join("/");
+    if path.starts_with('/') {
+        format!("/{}", normalized)
+    } else {
+        normalized
+    }
+}
@@ -0,0 +1,416 @@
+//! Project-wide symbol table
+//!
+//! Indexes the outline of every parsed file by qualified name and answers
+//! go-to-definition and find-references queries without a language server.
+//! Names are looked up the way the languages scope them: declarations of
+//! the file itself, then its imports (following re-exports and aliases),
+//! then the file's package for Go and Java, and finally exported symbols
+//! anywhere in the project. Test chunks are linked back to the symbols they
+//! exercise.
+
+use crate::resolver::{extension, normalize, parent_dir, Project, ResolveOptions};
+use crate::types::{
+    BindingKind, Definition, IdentifierCount, IdentifierKind, ImportInfo, ParseResult, SymbolInfo,
+    SymbolReference,
+};
+use std::collections::{HashMap, HashSet};
+
+/// `find_definition` results within one query, by name and file
+type DefinitionCache<'a> = HashMap<(String, String), Vec<&'a Definition>>;
+
+/// Definitions and references of a batch of parsed files
+#[derive(Debug, Default)]
+pub struct SymbolTable {
+    /// Definitions by qualified name; the same name may be defined in several files
+    by_qualified_name: HashMap<String, Vec<Definition>>,
+    files: HashMap<String, FileIndex>,
+}
+
+/// What the table keeps of a single file
+#[derive(Debug, Default)]
+struct FileIndex {
+    definitions: Vec<Definition>,
+    imports: Vec<ImportInfo>,
+    /// Resolved files of each import, parallel to `imports`
+    import_targets: Vec<Vec<String>>,
+    /// Exported name → local name (`export { a as b }`, `export default a`)
+    export_aliases: HashMap<String, String>,
+    chunks: Vec<ChunkReferences>,
+}
+
+#[derive(Debug)]
+struct ChunkReferences {
+    id: String,
+    start_line: usize,
+    end_line: usize,
+    references: Vec<IdentifierCount>,
+    /// Symbols exercised by a test chunk
+    test_subjects: Vec<String>,
+}
+
+impl SymbolTable {
+    /// Index every definition of the given files
+    ///
+    /// Paths are project-relative with `/` separators, as for
+    /// [`resolve_imports`](crate::resolve_imports).
+    pub fn build(files: &[(&str, &ParseResult)], options: &ResolveOptions) -> Self {
+        let project = Project::new(files);
+        let mut table = SymbolTable::default();
+
+        for (path, result) in files {
+            let path = normalize(path);
+            let mut index = FileIndex {
+                imports: result.imports.clone(),
+                import_targets: result
+                    .imports
+                    .iter()
+                    .map(|import| project.targets(&path, import, options))
+                    .collect(),
+                ..FileIndex::default()
+            };
+
+            for export in result.exports.iter().filter(|e| e.source.is_none()) {
+                if let Some(local) = &export.local {
+                    index.export_aliases.insert(export.name.clone(), local.clone());
+                }
+            }
+
+            flatten(&result.symbols, &path, result, &mut index.definitions);
+            for definition in &index.definitions {
+                table
+                    .by_qualified_name
+                    .entry(definition.qualified_name.clone())
+                    .or_default()
+                    .push(definition.clone());
+            }
+
+            index.chunks = result
+                .chunks
+                .iter()
+                .map(|chunk| ChunkReferences {
+                    id: chunk.id.clone(),
+                    start_line: chunk.start_line,
+                    end_line: chunk.end_line,
+                    references: chunk.references.clone(),
+                    test_subjects: chunk.test_subjects.clone(),
+                })
+                .collect();
+
+            table.files.insert(path, index);
+        }
+
+        table
+    }
+
+    /// Definitions with the given qualified name (`Calculator.add`, `parser::PrismParser`)
+    pub fn lookup(&self, qualified_name: &str) -> &[Definition] {
+        self.by_qualified_name.get(qualified_name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every definition of a file, parents before their members
+    pub fn definitions_in(&self, file: &str) -> &[Definition] {
+        self.files.get(&normalize(file)).map_or(&[], |index| index.definitions.as_slice())
+    }
+
+    /// Definitions `name` refers to when used in `from_file`, best match first
+    ///
+    /// `name` may be qualified (`ns.helper`, `Calculator.add`, `types::Node`).
+    pub fn find_definition(&self, name: &str, from_file: &str) -> Vec<&Definition> {
+        let from_file = normalize(from_file);
+
+        let found = self.resolve_in_file(&from_file, name, &mut HashSet::new());
+        if !found.is_empty() {
+            return found;
+        }
+
+        // Go and Java share one scope across the files of a package
+        if matches!(extension(&from_file), "go" | "java") {
+            let package = parent_dir(&from_file);
+            let found: Vec<&Definition> = self
+                .files
+                .iter()
+                .filter(|(path, _)| *path != &from_file && parent_dir(path) == package)
+                .filter(|(path, _)| extension(path) == extension(&from_file))
+                .flat_map(|(path, _)| self.local_definitions(path, name))
+                .collect();
+            if !found.is_empty() {
+                return found;
+            }
+        }
+
+        // Anything exported under that name elsewhere in the project
+        let mut found: Vec<&Definition> = self
+            .lookup(name)
+            .iter()
+            .chain(self.files.values().flat_map(|index| {
+                index
+                    .definitions
+                    .iter()
+                    .filter(|d| d.parent_id.is_none() && d.name == name && d.qualified_name != name)
+            }))
+            .filter(|d| d.is_exported)
+            .collect();
+        found.sort_by(|a, b| a.file.cmp(&b.file).then(a.start_line.cmp(&b.start_line)));
+        found
+    }
+
+    /// Chunks that use `symbol`, with how often they mention it
+    ///
+    /// A use counts when the name resolves to `symbol` from the chunk's file.
+    /// Members are matched by name wherever their owner type is visible.
+    pub fn find_references(&self, symbol: &Definition) -> Vec<SymbolReference> {
+        let owner = symbol.parent_id.as_ref().and_then(|parent_id| {
+            self.files
+                .get(&symbol.file)?
+                .definitions
+                .iter()
+                .find(|d| &d.id == parent_id)
+        });
+
+        let mut paths: Vec<&String> = self.files.keys().collect();
+        paths.sort();
+
+        let mut cache = DefinitionCache::new();
+        let mut references = Vec::new();
+        for path in paths {
+            let index = &self.files[path];
+
+            // Names under which the symbol (or its owner type) is visible here,
+            // including import aliases: `import { format as fmt }`
+            let target = owner.unwrap_or(symbol);
+            let mut names: Vec<&str> = Vec::new();
+            if *path == target.file || self.refers_to(&target.name, path, target, &mut cache) {
+                names.push(&target.name);
+            }
+            for binding in index.imports.iter().flat_map(|import| &import.bindings) {
+                let is_alias = matches!(binding.kind, BindingKind::Named | BindingKind::Default)
+                    && binding.local != target.name;
+                if is_alias && self.refers_to(&binding.local, path, target, &mut cache) {
+                    names.push(&binding.local);
+                }
+            }
+
+            // `import * as ns` makes `ns.name` a property reference
+            let via_namespace = owner.is_none()
+                && index.imports.iter().zip(&index.import_targets).any(|(import, targets)| {
+                    targets.contains(&symbol.file) && import.bindings.iter().any(|b| b.kind == BindingKind::Namespace)
+                });
+
+            for chunk in &index.chunks {
+                let count: usize = chunk
+                    .references
+                    .iter()
+                    .filter(|r| match (owner, r.kind) {
+                        // Members are used as `value.member`
+                        (Some(_), IdentifierKind::Property) => r.name == symbol.name && !names.is_empty(),
+                        (Some(_), _) => false,
+                        (None, IdentifierKind::Property) => r.name == symbol.name && via_namespace,
+                        (None, _) => names.contains(&r.name.as_str()),
+                    })
+                    .map(|r| r.count)
+                    .sum();
+
+                if count > 0 {
+                    references.push(SymbolReference {
+                        file: path.clone(),
+                        chunk_id: chunk.id.clone(),
+                        start_line: chunk.start_line,
+                        end_line: chunk.end_line,
+                        count,
+                    });
+                }
+            }
+        }
+
+        references
+    }
+
+    /// Test chunks that exercise `symbol`, with how many of their subjects
+    /// resolve to it
+    pub fn find_tests(&self, symbol: &Definition) -> Vec<SymbolReference> {
+        let mut paths: Vec<&String> = self.files.keys().collect();
+        paths.sort();
+
+        let mut cache = DefinitionCache::new();
+        let mut tests = Vec::new();
+        for path in paths {
+            for chunk in &self.files[path].chunks {
+                let count = chunk
+                    .test_subjects
+                    .iter()
+                    .filter(|subject| self.refers_to(subject, path, symbol, &mut cache))
+                    .count();
+                if count > 0 {
+                    tests.push(SymbolReference {
+                        file: path.clone(),
+                        chunk_id: chunk.id.clone(),
+                        start_line: chunk.start_line,
+                        end_line: chunk.end_line,
+                        count,
+                    });
+                }
+            }
+        }
+        tests
+    }
+
+    /// Whether `name` used in `file` resolves to `definition`
+    fn refers_to<'a>(
+        &'a self,
+        name: &str,
+        file: &str,
+        definition: &Definition,
+        cache: &mut DefinitionCache<'a>,
+    ) -> bool {
+        cache
+            .entry((name.to_string(), file.to_string()))
+            .or_insert_with(|| self.find_definition(name, file))
+            .iter()
+            .any(|d| d.file == definition.file && d.id == definition.id)
+    }
+
+    /// Resolve a name through a file's own declarations and its imports
+    ///
+    /// Each file and name is looked up once per query, so barrels that
+    /// re-export each other end the search instead of looping.
+    fn resolve_in_file(&self, file: &str, name: &str, visited: &mut HashSet<(String, String)>) -> Vec<&Definition> {
+        if !visited.insert((file.to_string(), name.to_string())) {
+            return Vec::new();
+        }
+        let Some(index) = self.files.get(file) else {
+            return Vec::new();
+        };
+
+        let local = self.local_definitions(file, name);
+        if !local.is_empty() {
+            return local;
+        }
+
+        // `export { a as b }`: importers ask for `b`, the file declares `a`
+        if let Some(local_name) = index.export_aliases.get(name).filter(|local| *local != name) {
+            let found = self.resolve_in_file(file, local_name, visited);
+            if !found.is_empty() {
+                return found;
+            }
+        }
+
+        let (head, rest) = split_qualified(name);
+        let mut found = Vec::new();
+        for (import, targets) in index.imports.iter().zip(&index.import_targets) {
+            for binding in &import.bindings {
+                let wanted: Vec<String> = match binding.kind {
+                    // The imported name may be a module itself: `use crate::parser; parser::Parser`
+                    BindingKind::Named | BindingKind::Default if binding.local == head => {
+                        std::iter::once(join_qualified(&binding.imported, rest, name))
+                            .chain(rest.map(str::to_string))
+                            .collect()
+                    }
+                    // `ns.helper` through `import * as ns`
+                    BindingKind::Namespace if binding.local == head => rest.map(str::to_string).into_iter().collect(),
+                    BindingKind::Wildcard => vec![name.to_string()],
+                    _ => Vec::new(),
+                };
+                for target in targets {
+                    if let Some(definitions) = wanted
+                        .iter()
+                        .map(|wanted| self.resolve_in_file(target, wanted, visited))
+                        .find(|definitions| !definitions.is_empty())
+                    {
+                        found.extend(definitions);
+                    }
+                }
+            }
+            if !found.is_empty() {
+                break;
+            }
+        }
+        found
+    }
+
+    /// Declarations of `name` in one file: by qualified name, then top-level
+    /// names, then members
+    fn local_definitions(&self, file: &str, name: &str) -> Vec<&Definition> {
+        let Some(index) = self.files.get(file) else {
+            return Vec::new();
+        };
+
+        let by_qualified: Vec<&Definition> = index.definitions.iter().filter(|d| d.qualified_name == name).collect();
+        if !by_qualified.is_empty() {
+            return by_qualified;
+        }
+
+        // A default export names its declaration by the local name
+        if name == "default" {
+            if let Some(local) = index.export_aliases.get("default") {
+                return self.local_definitions(file, local);
+            }
+        }
+
+        let top_level: Vec<&Definition> = index
+            .definitions
+            .iter()
+            .filter(|d| d.parent_id.is_none() && d.name == name)
+            .collect();
+        if !top_level.is_empty() {
+            return top_level;
+        }
+        index.definitions.iter().filter(|d| d.name == name).collect()
+    }
+}
+
+/// Flatten an outline into definitions, parents first
+fn flatten(symbols: &[SymbolInfo], file: &str, result: &ParseResult, definitions: &mut Vec<Definition>) {
+    for symbol in symbols {
+        definitions.push(Definition {
+            file: file.to_string(),
+            id: symbol.id.clone(),
+            name: symbol.name.clone(),
+            qualified_name: symbol.qualified_name.clone(),
+            kind: symbol.kind,
+            start_line: symbol.start_line,
+            end_line: symbol.end_line,
+            parent_id: symbol.parent_id.clone(),
+            is_exported: is_exported(symbol, result),
+        });
+        flatten(&symbol.children, file, result, definitions);
+    }
+}
+
+/// Whether a symbol is visible to other files
+fn is_exported(symbol: &SymbolInfo, result: &ParseResult) -> bool {
+    let by_export = symbol.parent_id.is_none()
+        && result
+            .exports
+            .iter()
+            .any(|e| e.source.is_none() && e.local.as_deref() == Some(symbol.name.as_str()));
+    let by_function = result
+        .functions
+        .iter()
+        .any(|f| f.name == symbol.name && f.end_line == symbol.end_line && f.is_exported);
+    let by_class = result
+        .classes
+        .iter()
+        .any(|c| c.name == symbol.name && c.end_line == symbol.end_line && c.is_exported);
+    by_export || by_function || by_class
+}
+
+/// Split `a.b.c` or `a::b::c` after its first segment
+fn split_qualified(name: &str) -> (&str, Option<&str>) {
+    let dot = name.find('.').map(|i| (i, 1));
+    let colons = name.find("::").map(|i| (i, 2));
+    match [dot, colons].into_iter().flatten().min() {
+        Some((i, len)) => (&name[..i], Some(&name[i + len..])),
+        None => (name, None),
+    }
+}
+
+/// Rebuild a qualified name on the imported side, keeping the separator
+fn join_qualified(head: &str, rest: Option<&str>, original: &str) -> String {
+    match rest {
+        Some(rest) => {
+            let separator = if original.contains("::") { "::" } else { "." };
+            format!("{}{}{}", head, separator, rest)
+        }
+        None => head.to_string(),
+    }
+}
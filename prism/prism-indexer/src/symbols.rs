@@ -0,0 +1,384 @@
+//! Symbol classification and the document outline
+//!
+//! Maps the declaration nodes of every grammar onto a single `SymbolKind`
+//! taxonomy and nests them into a tree with qualified names, so consumers
+//! no longer have to guess from node types or line ranges.
+
+use crate::extractor::{
+    class_name, function_binding, function_declarator, function_name, node_text, rust_type_name, FileContext,
+};
+use crate::types::{SymbolInfo, SymbolKind};
+use crate::visibility::{enclosing_type, is_top_level};
+use std::collections::HashMap;
+use tree_sitter::Node;
+
+/// A symbol collected during the walk, before the tree is assembled
+struct Entry {
+    symbol: SymbolInfo,
+    /// Index of the lexically enclosing symbol
+    parent: Option<usize>,
+    /// Qualified name of the type that owns a member declared outside of it
+    /// (Rust `impl` blocks, Go receivers, C++ `Type::method` definitions)
+    owner: Option<String>,
+}
+
+/// Build the symbol outline of a file
+///
+/// Returns the top-level symbols; methods, nested functions, inner types and
+/// the contents of modules and namespaces are attached as children.
+pub fn extract_symbols(root: &Node, ctx: &FileContext) -> Vec<SymbolInfo> {
+    let mut entries = Vec::new();
+    collect_symbols(root, ctx, None, "", &mut entries);
+    build_tree(entries)
+}
+
+/// Walk the tree and record every node that declares a symbol
+fn collect_symbols(node: &Node, ctx: &FileContext, parent: Option<usize>, scope: &str, entries: &mut Vec<Entry>) {
+    let separator = scope_separator(ctx.language);
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let named = symbol_kind(&child, ctx)
+            .and_then(|kind| symbol_name(&child, kind, ctx.source).map(|name| (kind, name)));
+
+        let Some((kind, name)) = named else {
+            collect_symbols(&child, ctx, parent, scope, entries);
+            continue;
+        };
+
+        let owner = member_owner(&child, ctx).map(|owner| qualify(scope, &owner, separator));
+        let qualified_name = qualify(owner.as_deref().unwrap_or(scope), &name, separator);
+
+        entries.push(Entry {
+            symbol: SymbolInfo {
+                id: String::new(),
+                name,
+                qualified_name: qualified_name.clone(),
+                kind,
+                start_line: child.start_position().row + 1,
+                end_line: child.end_position().row + 1,
+                parent_id: None,
+                children: Vec::new(),
+            },
+            parent,
+            owner,
+        });
+
+        let index = entries.len() - 1;
+        collect_symbols(&child, ctx, Some(index), &qualified_name, entries);
+    }
+}
+
+/// Assign ids, resolve out-of-line owners and nest the entries
+fn build_tree(mut entries: Vec<Entry>) -> Vec<SymbolInfo> {
+    // Overloads share a qualified name, so later ones get a `#n` suffix
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for entry in entries.iter_mut() {
+        let base = format!("{}:{}", entry.symbol.kind.as_str(), entry.symbol.qualified_name);
+        let count = seen.entry(base.clone()).or_insert(0);
+        *count += 1;
+        entry.symbol.id = if *count == 1 { base } else { format!("{}#{}", base, count) };
+    }
+
+    let parents: Vec<Option<usize>> = entries
+        .iter()
+        .map(|entry| {
+            let owner = entry.owner.as_ref().and_then(|owner| {
+                entries
+                    .iter()
+                    .position(|e| e.symbol.kind.is_type() && &e.symbol.qualified_name == owner)
+            });
+            owner.or(entry.parent)
+        })
+        .collect();
+
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); entries.len()];
+    let mut roots = Vec::new();
+    for (index, parent) in parents.iter().enumerate() {
+        match parent {
+            Some(parent) => children[*parent].push(index),
+            None => roots.push(index),
+        }
+    }
+
+    for (index, parent) in parents.iter().enumerate() {
+        entries[index].symbol.parent_id = parent.map(|p| entries[p].symbol.id.clone());
+    }
+
+    let mut symbols: Vec<Option<SymbolInfo>> = entries.into_iter().map(|e| Some(e.symbol)).collect();
+    roots.iter().map(|&root| assemble(root, &mut symbols, &children)).collect()
+}
+
+/// Move a symbol and its descendants out of the arena
+fn assemble(index: usize, symbols: &mut [Option<SymbolInfo>], children: &[Vec<usize>]) -> SymbolInfo {
+    let mut symbol = symbols[index].take().expect("symbol assembled twice");
+    symbol.children = children[index]
+        .iter()
+        .map(|&child| assemble(child, symbols, children))
+        .collect();
+    symbol
+}
+
+/// Nesting depth and enclosing symbol of a declaration
+///
+/// Depth counts the functions and types around the node; modules and
+/// namespaces only contribute to the qualified name. Members declared out of
+/// line (Rust `impl` blocks, Go receivers) report their owner type as the
+/// enclosing symbol but stay at depth 0, since they are not nested.
+pub(crate) fn symbol_scope(node: &Node, ctx: &FileContext) -> (usize, Option<String>) {
+    let separator = scope_separator(ctx.language);
+
+    let mut ancestors = Vec::new();
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if let Some(kind) = symbol_kind(&ancestor, ctx) {
+            if let Some(name) = symbol_name(&ancestor, kind, ctx.source) {
+                ancestors.push((ancestor, kind, name));
+            }
+        }
+        current = ancestor.parent();
+    }
+
+    let mut scope = String::new();
+    let mut depth = 0;
+    for (ancestor, kind, name) in ancestors.iter().rev() {
+        let owner = member_owner(ancestor, ctx).map(|owner| qualify(&scope, &owner, separator));
+        scope = qualify(owner.as_deref().unwrap_or(&scope), name, separator);
+        if !matches!(kind, SymbolKind::Module | SymbolKind::Constant) {
+            depth += 1;
+        }
+    }
+
+    if let Some(owner) = member_owner(node, ctx) {
+        scope = qualify(&scope, &owner, separator);
+    }
+
+    (depth, (!scope.is_empty()).then_some(scope))
+}
+
+/// The type a member belongs to when it is declared outside the type body
+fn member_owner(node: &Node, ctx: &FileContext) -> Option<String> {
+    match node.kind() {
+        // Rust: `impl Type { fn method() }`
+        "function_item" | "function_signature_item" | "const_item" => {
+            let owner = enclosing_type(node).filter(|owner| owner.kind() == "impl_item")?;
+            rust_type_name(&owner.child_by_field_name("type")?, ctx.source)
+        }
+        // Go: `func (c *Calc) Add()`
+        "method_declaration" if ctx.language == "go" => {
+            let receiver = node.child_by_field_name("receiver")?.named_child(0)?;
+            let mut receiver_type = receiver.child_by_field_name("type")?;
+            loop {
+                receiver_type = match receiver_type.kind() {
+                    "pointer_type" => receiver_type.named_child(0)?,
+                    "generic_type" => receiver_type.child_by_field_name("type")?,
+                    _ => break,
+                };
+            }
+            Some(node_text(&receiver_type, ctx.source))
+        }
+        // C++: `int ns::Calc<T>::add(int x) { ... }`
+        "function_definition" | "declaration" => {
+            let mut qualified = function_declarator(node)?.child_by_field_name("declarator")?;
+            let mut scopes = Vec::new();
+            while qualified.kind() == "qualified_identifier" {
+                if let Some(scope) = qualified.child_by_field_name("scope") {
+                    let scope = match scope.kind() {
+                        "template_type" => scope.child_by_field_name("name").unwrap_or(scope),
+                        _ => scope,
+                    };
+                    scopes.push(node_text(&scope, ctx.source));
+                }
+                qualified = qualified.child_by_field_name("name")?;
+            }
+            (!scopes.is_empty()).then(|| scopes.join("::"))
+        }
+        _ => None,
+    }
+}
+
+/// Join a scope and a name with the language's separator
+pub(crate) fn qualify(scope: &str, name: &str, separator: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}{}{}", scope, separator, name)
+    }
+}
+
+/// Path separator used in qualified names
+pub(crate) fn scope_separator(language: &str) -> &'static str {
+    match language {
+        "rust" | "cpp" | "c++" => "::",
+        _ => ".",
+    }
+}
+
+/// Classify a declaration node, or `None` if it declares no symbol
+pub(crate) fn symbol_kind(node: &Node, ctx: &FileContext) -> Option<SymbolKind> {
+    let kind = match node.kind() {
+        "class_declaration" | "abstract_class_declaration" | "class" | "class_definition" |
+        "record_declaration" => SymbolKind::Class,
+        "interface_declaration" | "annotation_type_declaration" => SymbolKind::Interface,
+        "enum_declaration" | "enum_item" => SymbolKind::Enum,
+        "struct_item" | "union_item" => SymbolKind::Struct,
+        "trait_item" => SymbolKind::Trait,
+        "type_alias_declaration" | "type_item" | "alias_declaration" | "type_alias" => SymbolKind::TypeAlias,
+
+        // C/C++: only definitions with a body declare a type
+        "class_specifier" if has_body(node) => SymbolKind::Class,
+        "struct_specifier" | "union_specifier" if has_body(node) => SymbolKind::Struct,
+        "enum_specifier" if has_body(node) => SymbolKind::Enum,
+        // `typedef struct { ... } Point;` is reported as the struct itself
+        "type_definition" => {
+            let defines_type = node.child_by_field_name("type").is_some_and(|t| {
+                matches!(t.kind(), "struct_specifier" | "union_specifier" | "enum_specifier") && has_body(&t)
+            });
+            if defines_type {
+                return None;
+            }
+            SymbolKind::TypeAlias
+        }
+
+        // Go: `type A struct{...}`, `type B interface{...}`, `type C int`
+        "type_spec" => match node.child_by_field_name("type")?.kind() {
+            "struct_type" => SymbolKind::Struct,
+            "interface_type" => SymbolKind::Interface,
+            _ => SymbolKind::TypeAlias,
+        },
+
+        // Python's root node is also called `module`
+        "internal_module" | "module" if is_typescript(ctx.language) => SymbolKind::Module,
+        "mod_item" => SymbolKind::Module,
+        "namespace_definition" if node.child_by_field_name("name").is_some() => SymbolKind::Module,
+
+        "const_item" | "const_spec" | "preproc_def" | "constant_declaration" => SymbolKind::Constant,
+        "static_item" if !has_child(node, "mutable_specifier") => SymbolKind::Constant,
+        "variable_declarator" if is_constant_binding(node, ctx) => SymbolKind::Constant,
+        "assignment" if is_python_constant(node, ctx) => SymbolKind::Constant,
+        "field_declaration" if ctx.language == "java" && is_static_final(node, ctx.source) => SymbolKind::Constant,
+
+        "method_definition" | "method_declaration" | "constructor_declaration" | "method_elem" |
+        "method_spec" | "method_signature" | "abstract_method_signature" => SymbolKind::Method,
+        "function_declaration" | "function_definition" | "function_item" | "function_signature_item" |
+        "generator_function_declaration" => member_or_function(node),
+        // Function values: `const f = () => {}`, `handle = () => {}` in a class
+        "arrow_function" | "function_expression" | "generator_function" => match function_binding(node)?.kind() {
+            "public_field_definition" | "field_definition" => SymbolKind::Method,
+            _ => SymbolKind::Function,
+        },
+        // C++ member and prototype declarations: `virtual void draw() const;`
+        "field_declaration" | "declaration" if function_declarator(node).is_some() => member_or_function(node),
+
+        _ => return None,
+    };
+
+    Some(kind)
+}
+
+/// `Method` for functions declared in a type (or defined as `Type::name`
+/// in C++), `Function` otherwise
+fn member_or_function(node: &Node) -> SymbolKind {
+    let qualified = function_declarator(node)
+        .and_then(|d| d.child_by_field_name("declarator"))
+        .is_some_and(|d| d.kind() == "qualified_identifier");
+
+    if qualified || enclosing_type(node).is_some() {
+        SymbolKind::Method
+    } else {
+        SymbolKind::Function
+    }
+}
+
+/// Resolve the name of a symbol node
+fn symbol_name(node: &Node, kind: SymbolKind, source: &str) -> Option<String> {
+    match kind {
+        SymbolKind::Function | SymbolKind::Method => function_name(node, source),
+        _ if node.kind() == "assignment" => node.child_by_field_name("left").map(|n| node_text(&n, source)),
+        _ if node.kind() == "field_declaration" || node.kind() == "constant_declaration" => {
+            let declarator = node.child_by_field_name("declarator")?;
+            declarator.child_by_field_name("name").map(|n| node_text(&n, source))
+        }
+        _ if node.kind() == "type_definition" => {
+            // Look through pointer and function declarators: `typedef int (*cb)(int);`
+            let mut declarator = node.child_by_field_name("declarator")?;
+            while declarator.kind() != "type_identifier" {
+                declarator = declarator.child_by_field_name("declarator")?;
+            }
+            Some(node_text(&declarator, source))
+        }
+        _ if node.child_by_field_name("name").is_some() => {
+            node.child_by_field_name("name").map(|n| node_text(&n, source))
+        }
+        _ => class_name(node, source),
+    }
+}
+
+/// Top-level `const` binding in TS/JS whose value is not a function or class
+fn is_constant_binding(node: &Node, ctx: &FileContext) -> bool {
+    if !is_typescript(ctx.language) {
+        return false;
+    }
+
+    let is_const = node
+        .parent()
+        .filter(|p| p.kind() == "lexical_declaration")
+        .and_then(|p| p.child_by_field_name("kind"))
+        .is_some_and(|k| k.kind() == "const");
+
+    let is_callable = node.child_by_field_name("value").is_some_and(|v| {
+        matches!(v.kind(), "arrow_function" | "function_expression" | "generator_function" | "class")
+    });
+
+    is_const && !is_callable && is_top_level(node)
+}
+
+/// Module-level Python assignment to an UPPER_CASE name
+fn is_python_constant(node: &Node, ctx: &FileContext) -> bool {
+    if ctx.language != "python" {
+        return false;
+    }
+
+    let at_module_level = node
+        .parent()
+        .filter(|p| p.kind() == "expression_statement")
+        .and_then(|p| p.parent())
+        .is_some_and(|p| p.kind() == "module");
+
+    let upper_case = node.child_by_field_name("left").is_some_and(|left| {
+        let name = node_text(&left, ctx.source);
+        left.kind() == "identifier"
+            && name.chars().any(|c| c.is_ascii_uppercase())
+            && !name.chars().any(|c| c.is_ascii_lowercase())
+    });
+
+    at_module_level && upper_case
+}
+
+/// Java `static final` fields
+fn is_static_final(node: &Node, source: &str) -> bool {
+    let mut cursor = node.walk();
+    let modifiers = node.children(&mut cursor).find(|n| n.kind() == "modifiers");
+    modifiers.is_some_and(|m| {
+        let modifiers = node_text(&m, source);
+        let mut words = modifiers.split_whitespace();
+        words.clone().any(|w| w == "static") && words.any(|w| w == "final")
+    })
+}
+
+/// Whether the language uses the TypeScript/JavaScript grammars
+fn is_typescript(language: &str) -> bool {
+    matches!(language, "typescript" | "javascript" | "tsx" | "jsx")
+}
+
+/// Whether a node has a `body` field
+fn has_body(node: &Node) -> bool {
+    node.child_by_field_name("body").is_some()
+}
+
+/// Whether a node has a direct child of the given kind
+fn has_child(node: &Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).any(|n| n.kind() == kind);
+    found
+}
@@ -0,0 +1,488 @@
+//! Test code detection and test-to-subject linking
+//!
+//! Recognises the test conventions of each language (Rust `#[test]` and
+//! `#[cfg(test)]` modules, Jest/Vitest `describe`/`it`/`test` blocks, pytest
+//! functions, classes and fixtures, Go `TestXxx(t *testing.T)`, JUnit
+//! annotations) and links tests to the symbols they exercise, from the test's
+//! name and the imported or local names it calls and references.
+
+use crate::extractor::{class_name, function_name, node_text};
+use crate::identifiers::Occurrence;
+use crate::types::{BindingKind, CallSite, FunctionInfo, IdentifierKind, ImportInfo, SymbolInfo};
+use std::collections::HashSet;
+use tree_sitter::Node;
+
+/// Jest, Vitest, Mocha and Jasmine blocks and hooks
+const TEST_CALLS: &[&str] = &[
+    "describe", "it", "test", "suite", "context", "fdescribe", "xdescribe", "fit", "xit", "xtest",
+    "beforeEach", "afterEach", "beforeAll", "afterAll", "before", "after",
+];
+
+/// Rust attributes that mark a test function (last path segment)
+const TEST_ATTRIBUTES: &[&str] = &["test", "rstest", "test_case", "bench", "quickcheck"];
+
+/// JUnit annotations of test methods and their fixtures
+const TEST_ANNOTATIONS: &[&str] = &[
+    "Test", "ParameterizedTest", "RepeatedTest", "TestFactory", "TestTemplate",
+    "BeforeEach", "AfterEach", "BeforeAll", "AfterAll", "Before", "After", "BeforeClass", "AfterClass",
+];
+
+/// Go test, benchmark, fuzz and example function prefixes
+const GO_TEST_PREFIXES: &[&str] = &["Test", "Benchmark", "Fuzz", "Example"];
+
+/// Modules whose imports are test tooling rather than code under test
+const TEST_FRAMEWORKS: &[&str] = &[
+    "vitest", "jest", "@jest/globals", "mocha", "chai", "sinon", "node:test", "node:assert", "assert",
+    "@testing-library", "pytest", "unittest", "testing", "org.junit", "org.assertj", "org.mockito",
+    "org.hamcrest",
+];
+
+/// Names of assertion helpers that never name a subject
+const ASSERTION_PREFIXES: &[&str] = &["assert", "expect"];
+
+/// Lines of test code, with the name that identifies the test
+#[derive(Debug)]
+struct TestRegion {
+    start_line: usize,
+    end_line: usize,
+    /// Function, class or module name, or the title of a `describe`/`it` block
+    name: Option<String>,
+}
+
+/// Test code of a file and the names a test may exercise
+pub(crate) struct TestIndex {
+    regions: Vec<TestRegion>,
+    /// Non-test names declared in the file or imported from non-test modules
+    known: HashSet<String>,
+    /// Helpers and fixtures declared in test code
+    test_names: HashSet<String>,
+    /// Locals bound to a whole module: `import * as utils`, Go packages
+    namespaces: HashSet<String>,
+    /// Whether unresolved names may come from the package or a wildcard
+    /// import (Go and Java packages, `use super::*`, `from m import *`)
+    open_scope: bool,
+}
+
+impl TestIndex {
+    /// Index the test code of a file from its imports and symbols; `None`
+    /// when it has no tests
+    pub(crate) fn new(
+        root: &Node,
+        source: &str,
+        language: &str,
+        imports: &[ImportInfo],
+        symbols: &[SymbolInfo],
+    ) -> Option<Self> {
+        let mut regions = Vec::new();
+        collect_regions(root, source, language, &mut regions);
+        if regions.is_empty() {
+            return None;
+        }
+
+        let mut known = HashSet::new();
+        let mut namespaces = HashSet::new();
+        let mut open_scope = matches!(language, "go" | "java");
+        for import in imports {
+            if is_test_framework(&import.source) || import.is_type_only {
+                continue;
+            }
+            for binding in import.bindings.iter().filter(|b| !b.is_type_only) {
+                match binding.kind {
+                    BindingKind::Wildcard => open_scope = true,
+                    BindingKind::Namespace => {
+                        namespaces.insert(binding.local.clone());
+                    }
+                    BindingKind::Named | BindingKind::Default => {
+                        known.insert(binding.local.clone());
+                    }
+                }
+            }
+        }
+
+        let mut test_names = HashSet::new();
+        collect_names(symbols, &regions, &mut known, &mut test_names);
+
+        Some(TestIndex {
+            regions,
+            known,
+            test_names,
+            namespaces,
+            open_scope,
+        })
+    }
+
+    /// Whether most of the given lines are test code
+    pub(crate) fn is_test_lines(&self, start_line: usize, end_line: usize) -> bool {
+        let lines = end_line.saturating_sub(start_line) + 1;
+        let covered = (start_line..=end_line).filter(|&line| covers(&self.regions, line, line)).count();
+        covered * 2 > lines
+    }
+
+    /// Symbols exercised by the test code within the given lines: names
+    /// matching the test's own name first, then callees and referenced names
+    /// among the file's calls and identifier occurrences
+    pub(crate) fn subjects(
+        &self,
+        start_line: usize,
+        end_line: usize,
+        calls: &[CallSite],
+        occurrences: &[Occurrence],
+    ) -> Vec<String> {
+        let in_range = |line: usize| line >= start_line && line <= end_line;
+        let mut subjects: Vec<String> = Vec::new();
+        let mut push = |name: String| {
+            if !subjects.contains(&name) {
+                subjects.push(name);
+            }
+        };
+
+        // Names declared inside the test itself are fixtures and locals
+        let declared: HashSet<&str> = occurrences
+            .iter()
+            .filter(|o| o.is_definition && in_range(o.line))
+            .map(|o| o.name.as_str())
+            .collect();
+        let referenced: HashSet<&str> = occurrences
+            .iter()
+            .filter(|o| !o.is_definition && in_range(o.line))
+            .map(|o| o.name.as_str())
+            .collect();
+
+        // `test_parse_config`, `TestParser`, `describe('Parser')`, `class ParserTest`
+        for region in self.regions.iter().filter(|r| r.start_line <= end_line && r.end_line >= start_line) {
+            if let Some(name) = region.name.as_deref().and_then(|name| self.name_match(name, &referenced)) {
+                push(name);
+            }
+        }
+
+        for call in calls.iter().filter(|c| in_range(c.line)) {
+            let head = call.receiver.as_deref().map(receiver_head);
+            match head {
+                // `utils.parse()`, `Parser::new()`, `Calculator.create()`
+                Some(head) if self.namespaces.contains(head) || self.known.contains(head) => {
+                    push(call.callee.clone())
+                }
+                Some(_) => {}
+                None if self.is_subject(&call.name, &declared) => push(call.name.clone()),
+                None => {}
+            }
+        }
+
+        for occurrence in occurrences.iter().filter(|o| !o.is_definition && in_range(o.line)) {
+            let is_named = matches!(occurrence.kind, IdentifierKind::Value | IdentifierKind::Type);
+            if is_named && !self.namespaces.contains(&occurrence.name) && self.known.contains(&occurrence.name) {
+                push(occurrence.name.clone());
+            }
+        }
+
+        // `utils` is only the path to `utils.clamp`
+        let receivers: Vec<String> = subjects
+            .iter()
+            .filter(|subject| subject.contains(['.', ':']))
+            .map(|subject| receiver_head(subject).to_string())
+            .collect();
+        subjects.retain(|subject| !receivers.contains(subject));
+        subjects
+    }
+
+    /// Whether a bare callee may be the code under test
+    fn is_subject(&self, name: &str, declared: &HashSet<&str>) -> bool {
+        if self.known.contains(name) {
+            return true;
+        }
+        self.open_scope
+            && !declared.contains(name)
+            && !self.test_names.contains(name)
+            && !TEST_CALLS.contains(&name)
+            && !ASSERTION_PREFIXES.iter().any(|prefix| name.to_lowercase().starts_with(prefix))
+    }
+
+    /// The longest known name that a test name or title starts with; in an
+    /// open scope, the stem itself when the test uses it (`TestAdd` calling `Add`)
+    fn name_match(&self, name: &str, referenced: &HashSet<&str>) -> Option<String> {
+        let stem = test_stem(name);
+        let normalized = normalize(stem);
+        if normalized.is_empty() {
+            return None;
+        }
+        let known = self
+            .known
+            .iter()
+            .filter(|known| {
+                let known = normalize(known);
+                !known.is_empty() && normalized.starts_with(&known)
+            })
+            .max_by(|a, b| a.len().cmp(&b.len()).then(b.cmp(a)))
+            .cloned();
+        known.or_else(|| (self.open_scope && referenced.contains(stem)).then(|| stem.to_string()))
+    }
+}
+
+/// Whether a function is test code: a test itself, a fixture or hook, or
+/// declared inside a test module, class or block
+pub(crate) fn is_test_function(node: &Node, source: &str, language: &str) -> bool {
+    if is_test_declaration(node, source, language) {
+        return true;
+    }
+
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if is_test_container(&ancestor, source, language) {
+            return true;
+        }
+        current = ancestor.parent();
+    }
+    false
+}
+
+/// Fill in the subjects of every test function of a file
+pub(crate) fn link_tests(
+    index: &TestIndex,
+    calls: &[CallSite],
+    occurrences: &[Occurrence],
+    functions: &mut [FunctionInfo],
+) {
+    for function in functions.iter_mut().filter(|f| f.is_test) {
+        function.test_subjects = index
+            .subjects(function.start_line, function.end_line, calls, occurrences)
+            .into_iter()
+            .filter(|subject| subject != &function.name)
+            .collect();
+    }
+}
+
+/// Walk the tree and record the lines of every test, test module and block
+fn collect_regions(node: &Node, source: &str, language: &str, regions: &mut Vec<TestRegion>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let name = if is_test_declaration(&child, source, language) {
+            Some(function_name(&child, source))
+        } else if is_test_container(&child, source, language) {
+            Some(container_name(&child, source))
+        } else {
+            None
+        };
+
+        if let Some(name) = name {
+            // Rust attributes and Python decorators belong to the test
+            let start = match child.parent() {
+                Some(parent) if parent.kind() == "decorated_definition" => parent,
+                _ => child,
+            };
+            regions.push(TestRegion {
+                start_line: start.start_position().row + 1,
+                end_line: child.end_position().row + 1,
+                name,
+            });
+        }
+
+        collect_regions(&child, source, language, regions);
+    }
+}
+
+/// Whether the given lines lie within a single test region
+fn covers(regions: &[TestRegion], start_line: usize, end_line: usize) -> bool {
+    regions
+        .iter()
+        .any(|r| r.start_line <= start_line && r.end_line >= end_line)
+}
+
+/// Sort the names of a file's declarations into non-test and test code
+fn collect_names(
+    symbols: &[SymbolInfo],
+    regions: &[TestRegion],
+    names: &mut HashSet<String>,
+    test_names: &mut HashSet<String>,
+) {
+    for symbol in symbols {
+        if covers(regions, symbol.start_line, symbol.end_line) {
+            test_names.insert(symbol.name.clone());
+        } else {
+            names.insert(symbol.name.clone());
+        }
+        collect_names(&symbol.children, regions, names, test_names);
+    }
+}
+
+/// Whether a function declaration is a test, fixture or hook by itself
+fn is_test_declaration(node: &Node, source: &str, language: &str) -> bool {
+    match (language, node.kind()) {
+        ("rust", "function_item") => preceding_attributes(node, source).iter().any(|attribute| {
+            let path = attribute.split('(').next().unwrap_or("").trim();
+            TEST_ATTRIBUTES.contains(&path.rsplit("::").next().unwrap_or(path))
+        }),
+        ("python", "function_definition") => {
+            // Methods are collected through their test class
+            let declaration = node.parent().filter(|p| p.kind() == "decorated_definition").unwrap_or(*node);
+            let is_module_level = declaration.parent().is_some_and(|p| p.kind() == "module");
+            let is_test_name = is_module_level
+                && function_name(node, source).is_some_and(|name| name.starts_with("test"));
+            let is_fixture = node
+                .parent()
+                .filter(|parent| parent.kind() == "decorated_definition")
+                .is_some_and(|parent| {
+                    let mut cursor = parent.walk();
+                    let found = parent
+                        .named_children(&mut cursor)
+                        .filter(|child| child.kind() == "decorator")
+                        .any(|decorator| node_text(&decorator, source).contains("fixture"));
+                    found
+                });
+            is_test_name || is_fixture
+        }
+        ("go", "function_declaration") => {
+            let Some(name) = function_name(node, source) else {
+                return false;
+            };
+            let takes_testing = node
+                .child_by_field_name("parameters")
+                .is_some_and(|params| node_text(&params, source).contains("testing."));
+            GO_TEST_PREFIXES.iter().any(|prefix| {
+                name.strip_prefix(prefix).is_some_and(|rest| {
+                    let is_word = rest.is_empty() || rest.starts_with(|c: char| !c.is_lowercase());
+                    is_word && (takes_testing || *prefix == "Example")
+                })
+            })
+        }
+        ("java", "method_declaration") => {
+            let mut cursor = node.walk();
+            let found = node
+                .children(&mut cursor)
+                .filter(|child| child.kind() == "modifiers")
+                .any(|modifiers| {
+                    let mut modifier_cursor = modifiers.walk();
+                    let found = modifiers.named_children(&mut modifier_cursor).any(|annotation| {
+                        matches!(annotation.kind(), "marker_annotation" | "annotation")
+                            && annotation
+                                .child_by_field_name("name")
+                                .is_some_and(|name| TEST_ANNOTATIONS.contains(&node_text(&name, source).as_str()))
+                    });
+                    found
+                });
+            found
+        }
+        _ => false,
+    }
+}
+
+/// Whether a node groups tests: Rust `#[cfg(test)]` modules, Jest blocks,
+/// pytest and unittest classes, JUnit test classes
+fn is_test_container(node: &Node, source: &str, language: &str) -> bool {
+    match (language, node.kind()) {
+        ("rust", "mod_item") => preceding_attributes(node, source)
+            .iter()
+            .any(|attribute| attribute.replace(' ', "") == "cfg(test)"),
+        ("typescript" | "tsx" | "javascript" | "jsx", "call_expression") => {
+            let is_test_call = node
+                .child_by_field_name("function")
+                .and_then(|function| callee_root(&function, source))
+                .is_some_and(|root| TEST_CALLS.contains(&root.as_str()));
+            // Blocks and hooks take a callback: `it('adds', () => {...})`
+            let takes_callback = node.child_by_field_name("arguments").is_some_and(|arguments| {
+                let mut cursor = arguments.walk();
+                let found = arguments
+                    .named_children(&mut cursor)
+                    .any(|argument| matches!(argument.kind(), "arrow_function" | "function_expression" | "function"));
+                found
+            });
+            is_test_call && takes_callback
+        }
+        ("python", "class_definition") => {
+            let is_test_name = class_name(node, source).is_some_and(|name| name.starts_with("Test"));
+            let is_test_case = node
+                .child_by_field_name("superclasses")
+                .is_some_and(|superclasses| node_text(&superclasses, source).contains("TestCase"));
+            is_test_name || is_test_case
+        }
+        ("java", "class_declaration") => class_name(node, source)
+            .is_some_and(|name| name.ends_with("Test") || name.ends_with("Tests") || name.ends_with("IT")),
+        _ => false,
+    }
+}
+
+/// Name of a test container: the module or class name, or a block's title
+fn container_name(node: &Node, source: &str) -> Option<String> {
+    if node.kind() != "call_expression" {
+        return class_name(node, source)
+            .or_else(|| node.child_by_field_name("name").map(|name| node_text(&name, source)));
+    }
+
+    let arguments = node.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let title = arguments.named_children(&mut cursor).next()?;
+    matches!(title.kind(), "string" | "template_string")
+        .then(|| node_text(&title, source).trim_matches(|c| c == '\'' || c == '"' || c == '`').to_string())
+}
+
+/// Identifier a callee chain starts from: `describe`, `it.only`, `test.each([...])`
+fn callee_root(node: &Node, source: &str) -> Option<String> {
+    match node.kind() {
+        "identifier" => Some(node_text(node, source)),
+        "member_expression" => callee_root(&node.child_by_field_name("object")?, source),
+        "call_expression" => callee_root(&node.child_by_field_name("function")?, source),
+        _ => None,
+    }
+}
+
+/// Contents of the attributes directly above a Rust item: `cfg(test)`, `tokio::test`
+fn preceding_attributes(node: &Node, source: &str) -> Vec<String> {
+    let mut attributes = Vec::new();
+    let mut current = node.prev_named_sibling();
+    while let Some(sibling) = current {
+        match sibling.kind() {
+            "attribute_item" => {
+                let mut cursor = sibling.walk();
+                attributes.extend(
+                    sibling
+                        .named_children(&mut cursor)
+                        .filter(|child| child.kind() == "attribute")
+                        .map(|attribute| node_text(&attribute, source)),
+                );
+            }
+            "line_comment" | "block_comment" => {}
+            _ => break,
+        }
+        current = sibling.prev_named_sibling();
+    }
+    attributes
+}
+
+/// Whether an import names test tooling: `vitest`, `@testing-library/react`, `org.junit.jupiter.api`
+fn is_test_framework(source: &str) -> bool {
+    TEST_FRAMEWORKS.iter().any(|framework| {
+        source == *framework
+            || source.starts_with(&format!("{}/", framework))
+            || source.starts_with(&format!("{}.", framework))
+    })
+}
+
+/// A test name without its conventional prefix or suffix, or the first word
+/// of a block title: `test_parse_config` → `parse_config`, `ParserTest` → `Parser`
+fn test_stem(name: &str) -> &str {
+    let name = name.split_whitespace().next().unwrap_or("");
+    let name = ["test_", "Test", "test", "Benchmark", "Fuzz", "Example"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name);
+    let name = ["_tests", "_test", "Tests", "Test", "Spec", "IT"]
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .unwrap_or(name);
+    name.trim_matches('_')
+}
+
+/// Lowercase a name and drop separators, so `parse_config` matches `parseConfig`
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// First segment of a receiver: `utils` of `utils.fs`, `Parser` of `Parser::new`
+fn receiver_head(receiver: &str) -> &str {
+    receiver
+        .split(['.', ':'])
+        .next()
+        .unwrap_or(receiver)
+}
@@ -0,0 +1,146 @@
+//! Token counting
+//!
+//! Chunk sizes are measured in model tokens. By default they are estimated
+//! from the byte length of the text; the opt-in `bpe` feature counts them
+//! exactly with byte-pair encoding. It bundles cl100k; other models'
+//! vocabularies, such as Llama 3's, are loaded in tiktoken format with
+//! [`BpeTokenizer::from_tiktoken`].
+
+use crate::error::{PrismError, Result};
+
+/// Names accepted by [`tokenizer_for`]
+#[cfg(feature = "bpe")]
+pub const TOKENIZERS: &[&str] = &["cl100k", "heuristic"];
+
+/// Names accepted by [`tokenizer_for`]
+#[cfg(not(feature = "bpe"))]
+pub const TOKENIZERS: &[&str] = &["heuristic"];
+
+/// Counts the tokens a model sees in a piece of text
+pub trait Tokenizer: Send + Sync {
+    /// Name the tokenizer is selected by
+    fn name(&self) -> &str;
+
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+/// Estimate of ~4 bytes per token, typical for English and ASCII code
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicTokenizer;
+
+impl Tokenizer for HeuristicTokenizer {
+    fn name(&self) -> &str {
+        "heuristic"
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+        // Minimum of 1 token for non-empty text
+        (text.len() / 4).max(1)
+    }
+}
+
+/// Look up a tokenizer by name
+pub fn tokenizer_for(name: &str) -> Result<Box<dyn Tokenizer>> {
+    match name {
+        "heuristic" => Ok(Box::new(HeuristicTokenizer)),
+        #[cfg(feature = "bpe")]
+        "cl100k" | "cl100k_base" => Ok(Box::new(BpeTokenizer::cl100k())),
+        // Meta's licence keeps the Llama 3 vocabulary out of the bundle
+        #[cfg(feature = "bpe")]
+        "llama3" => Err(PrismError::TokenizerError(
+            "The llama3 vocabulary is not bundled; load its tokenizer.model with set_tokenizer_vocabulary".to_string(),
+        )),
+        _ => Err(PrismError::UnsupportedTokenizer(name.to_string())),
+    }
+}
+
+/// cl100k when its vocabulary is available, the heuristic otherwise
+pub fn default_tokenizer() -> Box<dyn Tokenizer> {
+    tokenizer_for("cl100k").unwrap_or_else(|_| Box::new(HeuristicTokenizer))
+}
+
+#[cfg(feature = "bpe")]
+pub use bpe::BpeTokenizer;
+
+#[cfg(feature = "bpe")]
+mod bpe {
+    use super::Tokenizer;
+    use crate::error::{PrismError, Result};
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use rustc_hash::FxHashMap;
+    use std::sync::Arc;
+    use tiktoken_rs::{CoreBPE, Rank};
+
+    /// Pre-tokenization shared by cl100k and Llama 3: words with their leading
+    /// space, digits in groups of three, punctuation runs and whitespace
+    const CL100K_PATTERN: &str = r"(?i:'s|'t|'re|'ve|'m|'ll|'d)|[^\r\n\p{L}\p{N}]?\p{L}+|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+(?!\S)|\s+";
+
+    /// Byte-pair encoding over a ranked vocabulary
+    #[derive(Clone)]
+    pub struct BpeTokenizer {
+        name: String,
+        bpe: Vocabulary,
+    }
+
+    #[derive(Clone)]
+    enum Vocabulary {
+        /// Loaded once and shared, as it takes a while to load
+        Bundled(&'static CoreBPE),
+        Loaded(Arc<CoreBPE>),
+    }
+
+    impl BpeTokenizer {
+        /// The vocabulary of GPT-4 and OpenAI's embedding models
+        pub fn cl100k() -> Self {
+            BpeTokenizer {
+                name: "cl100k".to_string(),
+                bpe: Vocabulary::Bundled(tiktoken_rs::cl100k_base_singleton()),
+            }
+        }
+
+        /// A vocabulary in tiktoken format: one base64 token and its rank per
+        /// line, split with the cl100k pattern
+        ///
+        /// Llama 3 splits text like cl100k, and the `tokenizer.model` Meta
+        /// ships with it is in this format.
+        pub fn from_tiktoken(name: &str, ranks: &str) -> Result<Self> {
+            let mut encoder: FxHashMap<Vec<u8>, Rank> = FxHashMap::default();
+            for (index, line) in ranks.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+                let invalid = || PrismError::TokenizerError(format!("Invalid vocabulary line {}", index + 1));
+                let (token, rank) = line.trim().split_once(' ').ok_or_else(invalid)?;
+                let token = STANDARD.decode(token).map_err(|_| invalid())?;
+                let rank = rank.parse().map_err(|_| invalid())?;
+                encoder.insert(token, rank);
+            }
+            if encoder.is_empty() {
+                return Err(PrismError::TokenizerError("Empty vocabulary".to_string()));
+            }
+
+            let bpe = CoreBPE::new(encoder, FxHashMap::default(), CL100K_PATTERN)
+                .map_err(|e| PrismError::TokenizerError(e.to_string()))?;
+            Ok(BpeTokenizer {
+                name: name.to_string(),
+                bpe: Vocabulary::Loaded(Arc::new(bpe)),
+            })
+        }
+    }
+
+    impl Tokenizer for BpeTokenizer {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn count_tokens(&self, text: &str) -> usize {
+            let bpe = match &self.bpe {
+                Vocabulary::Bundled(bpe) => bpe,
+                Vocabulary::Loaded(bpe) => bpe.as_ref(),
+            };
+            // Special tokens in source code are plain text
+            bpe.encode_ordinary(text).len()
+        }
+    }
+}
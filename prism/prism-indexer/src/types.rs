@@ -13,31 +13,345 @@ pub struct SourceLocation {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionInfo {
     pub name: String,
+    /// `Function` or `Method`
+    pub kind: SymbolKind,
     pub signature: String,
     pub start_line: usize,
     pub end_line: usize,
-    pub parameters: Vec<String>,
+    pub parameters: Vec<Parameter>,
     pub return_type: Option<String>,
     pub is_async: bool,
     pub is_exported: bool,
+    pub visibility: Visibility,
+    /// Whether this is a component that returns JSX (capitalised name)
+    pub is_component: bool,
+    /// Number of enclosing functions and types (0 for top-level functions)
+    pub depth: usize,
+    /// Qualified name of the enclosing function or type
+    pub enclosing_symbol: Option<String>,
+    pub documentation: Option<Documentation>,
+    /// Calls made in the body, excluding those of named nested functions
+    pub calls: Vec<CallSite>,
+    /// Test, fixture or hook, or declared inside a test module, class or block
+    pub is_test: bool,
+    /// Names of the symbols a test exercises, resolvable from its file
+    pub test_subjects: Vec<String>,
+    pub metrics: ComplexityMetrics,
+}
+
+/// Size and complexity of a function
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplexityMetrics {
+    /// McCabe complexity: 1 plus branches, loops, cases, handlers and `&&`/`||`
+    pub cyclomatic: usize,
+    /// Control flow weighted by nesting, as defined by SonarSource
+    pub cognitive: usize,
+    /// Deepest nesting of control structures
+    pub max_nesting: usize,
+    pub statements: usize,
+    /// Parameters, not counting `self`/`this` receivers
+    pub parameters: usize,
+    /// Lines with code, excluding blank and comment-only lines
+    pub lines_of_code: usize,
+}
+
+/// A call made from a function or chunk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallSite {
+    /// Callee expression as written (`this.store.get`, `Vec::new`)
+    pub callee: String,
+    /// Simple name used to look the callee up (`get`, `new`)
+    pub name: String,
+    /// Receiver or path the callee is reached through (`this.store`, `Vec`)
+    pub receiver: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    /// `await f()` or Rust `f().await`
+    pub is_awaited: bool,
+}
+
+/// Visibility of a declaration, normalised across languages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    /// Visible to importers (`export`, `pub`, Go capitalised names, Java `public`)
+    Public,
+    /// Visible to subclasses (`protected`)
+    Protected,
+    /// Visible within the crate or package (`pub(crate)`, Go unexported,
+    /// Java package-private, Python `_name`)
+    Internal,
+    /// Visible only in the defining module or type
+    Private,
+}
+
+/// Kind of a declared symbol, aligned with LSP `SymbolKind`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    Class,
+    Interface,
+    Struct,
+    Enum,
+    Trait,
+    TypeAlias,
+    Constant,
+    Module,
+    Method,
+    Function,
+}
+
+impl SymbolKind {
+    /// Numeric LSP `SymbolKind` value
+    ///
+    /// LSP has no trait or type alias kinds; traits map to `Interface` and
+    /// type aliases to `TypeParameter`, as most language servers do.
+    pub fn lsp_kind(&self) -> u32 {
+        match self {
+            SymbolKind::Module => 2,
+            SymbolKind::Class => 5,
+            SymbolKind::Method => 6,
+            SymbolKind::Enum => 10,
+            SymbolKind::Interface | SymbolKind::Trait => 11,
+            SymbolKind::Function => 12,
+            SymbolKind::Constant => 14,
+            SymbolKind::Struct => 23,
+            SymbolKind::TypeAlias => 26,
+        }
+    }
+
+    /// Serialized name of the kind (`type_alias`, `method`, ...)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SymbolKind::Class => "class",
+            SymbolKind::Interface => "interface",
+            SymbolKind::Struct => "struct",
+            SymbolKind::Enum => "enum",
+            SymbolKind::Trait => "trait",
+            SymbolKind::TypeAlias => "type_alias",
+            SymbolKind::Constant => "constant",
+            SymbolKind::Module => "module",
+            SymbolKind::Method => "method",
+            SymbolKind::Function => "function",
+        }
+    }
+
+    /// Whether symbols of this kind can own methods
+    pub fn is_type(&self) -> bool {
+        matches!(
+            self,
+            SymbolKind::Class | SymbolKind::Interface | SymbolKind::Struct | SymbolKind::Enum | SymbolKind::Trait
+        )
+    }
+}
+
+/// A node of the document symbol outline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolInfo {
+    /// Stable id built from the kind and qualified name (`method:Calculator.add`)
+    pub id: String,
+    pub name: String,
+    /// Name including every enclosing scope (`Calculator.add`, `parser::PrismParser::new`)
+    pub qualified_name: String,
+    pub kind: SymbolKind,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub parent_id: Option<String>,
+    /// Members, nested functions and inner types, in source order
+    pub children: Vec<SymbolInfo>,
+}
+
+/// A single function parameter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Parameter {
+    /// Parameter name (or the full pattern for destructured parameters)
+    pub name: String,
+    /// Declared type, without the leading `:` where the syntax has one
+    pub type_annotation: Option<String>,
+    /// Default value expression
+    pub default_value: Option<String>,
+    /// Rest/variadic parameter (`...args`, `*args`, `String...`, `...string`)
+    pub is_variadic: bool,
+    /// Optional parameter (`x?: T` or any parameter with a default)
+    pub is_optional: bool,
 }
 
 /// Class information extracted from code
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClassInfo {
     pub name: String,
+    /// Class, interface, struct, enum or trait
+    pub kind: SymbolKind,
+    /// Primary base class, where the language has one
     pub extends: Option<String>,
+    /// Every other supertype: interfaces, mixins, super-interfaces,
+    /// embedded Go types and implemented Rust traits
     pub implements: Vec<String>,
     pub methods: Vec<FunctionInfo>,
     pub start_line: usize,
     pub end_line: usize,
+    pub is_exported: bool,
+    pub visibility: Visibility,
+    /// Number of enclosing functions and types (0 for top-level types)
+    pub depth: usize,
+    /// Qualified name of the enclosing function or type
+    pub enclosing_symbol: Option<String>,
+    pub documentation: Option<Documentation>,
+}
+
+/// Documentation attached to a declaration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Documentation {
+    /// Line where the doc comment or docstring starts
+    pub start_line: usize,
+    /// Description without comment markers or block tags
+    pub text: String,
+    /// `@param` / `:param:` entries
+    pub params: Vec<DocParam>,
+    /// `@returns` / `:returns:` description
+    pub returns: Option<String>,
+    /// `@throws` / `:raises:` entries, type first
+    pub throws: Vec<String>,
+}
+
+/// A documented parameter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocParam {
+    pub name: String,
+    pub type_annotation: Option<String>,
+    pub description: String,
 }
 
 /// Import/Export information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportInfo {
+    /// Module specifier as written (`./utils`, `..models`, `std::io`, `net/http`)
     pub source: String,
+    /// Local names the import binds
     pub imported_names: Vec<String>,
+    pub bindings: Vec<ImportBinding>,
+    pub kind: ImportKind,
+    /// Python relative import level (number of leading dots)
+    pub relative_level: usize,
+    /// The whole statement is erased: `import type`, `export type ... from`,
+    /// Python imports under `if TYPE_CHECKING:`
+    pub is_type_only: bool,
+    pub location: SourceLocation,
+}
+
+/// How a module is imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportKind {
+    /// Import statements, `use`, `#include`
+    Static,
+    /// CommonJS `require()` and TS `import x = require()`
+    Require,
+    /// Dynamic `import()`
+    Dynamic,
+}
+
+/// A single name introduced by an import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportBinding {
+    /// Name in the source module: `default` for default imports, `*` for
+    /// namespace and wildcard imports
+    pub imported: String,
+    /// Name bound in this file (`*` for wildcard imports)
+    pub local: String,
+    pub kind: BindingKind,
+    /// Erased at compile time (`import { type A }`), so not a runtime edge
+    pub is_type_only: bool,
+}
+
+/// Shape of an import binding or export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BindingKind {
+    /// `import a from 'm'`
+    Default,
+    /// `import { a } from 'm'`, `from m import a`, `use m::a`
+    Named,
+    /// The whole module under one name: `import * as m`, `import m`, Go packages
+    Namespace,
+    /// Every export merged into scope: `from m import *`, `use m::*`, Go dot imports
+    Wildcard,
+}
+
+/// A symbol defined somewhere in a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Definition {
+    /// File declaring the symbol
+    pub file: String,
+    /// Outline id of the symbol within its file
+    pub id: String,
+    pub name: String,
+    pub qualified_name: String,
+    pub kind: SymbolKind,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Outline id of the enclosing symbol
+    pub parent_id: Option<String>,
+    /// Visible to other files (`export`, `pub`, `public`, `__all__`)
+    pub is_exported: bool,
+}
+
+/// A chunk that uses a symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolReference {
+    pub file: String,
+    pub chunk_id: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Number of uses within the chunk
+    pub count: usize,
+}
+
+/// An import resolved to a file of the project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedImport {
+    /// Importing file
+    pub from: String,
+    /// Imported file
+    pub to: String,
+    /// Module specifier as written
+    pub source: String,
+    /// Erased at compile time, so not a runtime dependency
+    pub is_type_only: bool,
+}
+
+/// An import that did not resolve to a project file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedImport {
+    /// Importing file
+    pub from: String,
+    /// Module specifier as written
+    pub source: String,
+    pub location: SourceLocation,
+}
+
+/// File-level dependency edges of a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportGraph {
+    pub edges: Vec<ResolvedImport>,
+    /// Local-looking imports with no matching file (`./missing`, `crate::gone`)
+    pub unresolved: Vec<UnresolvedImport>,
+    /// Standard library and third-party imports
+    pub external: Vec<UnresolvedImport>,
+}
+
+/// A name a module makes available to importers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportInfo {
+    /// Name importers see: `default` for default exports, `*` for wildcard re-exports
+    pub name: String,
+    /// Local name, or the name in `source` for re-exports (`a` in `export { a as b }`)
+    pub local: Option<String>,
+    /// Module the name is re-exported from (`./button`, `crate::parser`)
+    pub source: Option<String>,
+    /// Default, named, namespace (`export * as ns`) or wildcard (`export *`)
+    pub kind: BindingKind,
+    /// `export type`, `export interface`, `export { type A }`
     pub is_type_only: bool,
     pub location: SourceLocation,
 }
@@ -45,16 +359,71 @@ pub struct ImportInfo {
 /// A code chunk for indexing
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodeChunk {
+    /// Changes whenever the chunk's file, symbol or normalized content does
     pub id: String,
+    /// Identity of the chunk's symbol within its file, kept across edits and moves
+    pub stable_id: String,
+    /// Hash of the normalized content, kept across moves and reformatting;
+    /// lines repeated from the previous chunk (`overlap`) are left out
+    pub content_hash: String,
     pub text: String,
     pub start_line: usize,
     pub end_line: usize,
     pub tokens: usize,
     pub language: String,
+    /// Kind of the main symbol in the chunk, if any
+    pub kind: Option<SymbolKind>,
+    /// Qualified name of the main symbol in the chunk, if any
+    pub qualified_name: Option<String>,
+    /// Documentation of the main symbol in the chunk, if any
+    pub documentation: Option<Documentation>,
     pub functions: Vec<FunctionInfo>,
     pub classes: Vec<ClassInfo>,
     pub imports: Vec<ImportInfo>,
     pub dependencies: Vec<String>,
+    /// Every call made within the chunk's lines
+    pub calls: Vec<CallSite>,
+    /// Names declared in the chunk: functions, types, members, variables, parameters
+    pub defines: Vec<IdentifierCount>,
+    /// Names used in the chunk without being declared there
+    pub references: Vec<IdentifierCount>,
+    /// Most of the chunk is test code
+    pub is_test: bool,
+    /// Names of the symbols the chunk's tests exercise
+    pub test_subjects: Vec<String>,
+    /// The chunk's lines contain syntax errors; its boundaries may come from
+    /// error recovery rather than the syntax tree
+    pub has_errors: bool,
+    /// Leading lines repeated from the end of the previous chunk, when the
+    /// chunk is a piece of a larger one
+    pub overlap: Option<LineRange>,
+}
+
+/// An inclusive, 1-based range of lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineRange {
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// Whether an identifier names a type, a value or a member
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdentifierKind {
+    /// Classes, interfaces, structs, type aliases and type parameters
+    Type,
+    /// Functions, variables, constants, parameters and modules
+    Value,
+    /// Fields, methods and object keys (`user.name`, `this.save()`)
+    Property,
+}
+
+/// An identifier and how often it occurs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentifierCount {
+    pub name: String,
+    pub kind: IdentifierKind,
+    pub count: usize,
 }
 
 /// Result of parsing code
@@ -65,12 +434,74 @@ pub struct ParseResult {
     pub chunks: Vec<CodeChunk>,
     pub functions: Vec<FunctionInfo>,
     pub classes: Vec<ClassInfo>,
+    pub imports: Vec<ImportInfo>,
+    pub exports: Vec<ExportInfo>,
+    /// Top-level symbols of the outline; nested symbols are their children
+    pub symbols: Vec<SymbolInfo>,
 }
 
-/// Error node information
+/// A syntax error found while parsing
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorNode {
+    /// What went wrong and where: `Missing "}" in class body`
     pub message: String,
     pub location: SourceLocation,
+    /// First line of the unparsed source, truncated; empty for missing tokens
     pub text: String,
+    pub severity: Severity,
+    /// Kind of the construct being parsed (`arguments`, `class_body`)
+    pub parent_kind: Option<String>,
+    /// Token or node the parser inserted to recover
+    pub missing: Option<String>,
+    /// Tokens the grammar accepts at the error, when there are only a few
+    pub expected: Vec<String>,
+}
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Source the parser could not make sense of
+    Error,
+    /// A separator the parser inserted without losing structure
+    Warning,
+}
+
+/// Whether nested functions and classes get chunks of their own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NestedSymbols {
+    /// Only top-level definitions are chunked; nested ones stay inside
+    /// their parent's chunk
+    #[default]
+    Exclude,
+    /// Methods, inner classes and nested functions also get their own
+    /// chunks, overlapping the chunk of their parent
+    Include,
+}
+
+/// How code is split into chunks
+///
+/// Unset fields take the defaults of the file's language.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChunkingOptions {
+    /// Size of the pieces an oversized chunk is split into, in tokens
+    pub target_tokens: Option<usize>,
+    /// Chunks larger than this are split, in tokens; defaults to
+    /// `MAX_CHUNK_SIZE`, or `target_tokens` when that is larger
+    pub max_tokens: Option<usize>,
+    /// Tokens shared by consecutive pieces of a split chunk or of long
+    /// top-level code
+    pub overlap_tokens: Option<usize>,
+    /// Top-level code shorter than this is not chunked on its own
+    pub min_lines: Option<usize>,
+    /// Top-level code and preprocessor blocks are split at this many lines
+    pub max_lines: Option<usize>,
+    /// Attach the file's imports to every chunk
+    pub include_imports: Option<bool>,
+    /// Start chunks at the doc comment of their definition
+    pub include_docs: Option<bool>,
+    /// Chunk nested functions and classes on their own
+    pub nested: Option<NestedSymbols>,
 }
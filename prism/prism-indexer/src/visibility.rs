@@ -0,0 +1,402 @@
+//! Visibility and export detection
+//!
+//! Every language spells "public" differently: TS/JS use `export` and
+//! CommonJS assignments, Rust has `pub`, Go capitalises names, Java uses
+//! access modifiers and Python relies on `__all__` and underscores.
+
+use crate::extractor::{node_text, FileContext};
+use crate::types::Visibility;
+use std::collections::HashSet;
+use tree_sitter::Node;
+
+/// Bodies that hold the members of a type
+const MEMBER_BODIES: &[&str] = &[
+    "class_body",
+    "block",
+    "declaration_list",
+    "field_declaration_list",
+    "interface_body",
+    "enum_body",
+];
+
+/// Declarations that own members
+const TYPE_DECLARATIONS: &[&str] = &[
+    "class_declaration",
+    "abstract_class_declaration",
+    "class",
+    "class_definition",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "impl_item",
+    "trait_item",
+    "class_specifier",
+    "struct_specifier",
+    "union_specifier",
+];
+
+/// Nodes that wrap a declaration without changing its scope
+const DECLARATION_WRAPPERS: &[&str] = &[
+    "export_statement",
+    "decorated_definition",
+    "template_declaration",
+    "lexical_declaration",
+    "variable_declaration",
+    "variable_declarator",
+    "expression_statement",
+];
+
+/// Root node kinds of each grammar
+const ROOT_NODES: &[&str] = &["program", "module", "source_file", "translation_unit"];
+
+/// Names a module exports by reference rather than by a declaration
+/// modifier: `export { a as b }`, `module.exports = ...`, `__all__`
+#[derive(Debug, Default)]
+pub(crate) struct ModuleExports {
+    names: HashSet<String>,
+    /// Python's `__all__` replaces the underscore convention when present
+    explicit: bool,
+}
+
+impl ModuleExports {
+    /// Collect the exported names declared at the top level of a file
+    pub(crate) fn collect(root: &Node, source: &str, language: &str) -> Self {
+        let mut exports = ModuleExports::default();
+
+        let mut cursor = root.walk();
+        for child in root.named_children(&mut cursor) {
+            match (language, child.kind()) {
+                ("python", "expression_statement") => exports.collect_python_all(&child, source),
+                (_, "export_statement") => exports.collect_export_clause(&child, source),
+                (_, "expression_statement") => exports.collect_commonjs(&child, source),
+                _ => {}
+            }
+        }
+
+        exports
+    }
+
+    /// Whether `name` is exported by reference
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.names.contains(name)
+    }
+
+    /// `export { a, b as c }` and `export default name`
+    fn collect_export_clause(&mut self, node: &Node, source: &str) {
+        // Re-exports (`export { a } from './a'`) don't export local declarations
+        if node.child_by_field_name("source").is_some() {
+            return;
+        }
+
+        if let Some(value) = node.child_by_field_name("value").filter(|v| v.kind() == "identifier") {
+            self.names.insert(node_text(&value, source));
+        }
+
+        let mut cursor = node.walk();
+        for clause in node.named_children(&mut cursor).filter(|n| n.kind() == "export_clause") {
+            let mut clause_cursor = clause.walk();
+            for specifier in clause.named_children(&mut clause_cursor) {
+                if let Some(name) = specifier.child_by_field_name("name") {
+                    self.names.insert(node_text(&name, source));
+                }
+            }
+        }
+    }
+
+    /// `module.exports = { a, b: c }`, `module.exports.a = a`, `exports.a = ...`
+    fn collect_commonjs(&mut self, node: &Node, source: &str) {
+        let Some(assignment) = node.named_child(0).filter(|n| n.kind() == "assignment_expression") else {
+            return;
+        };
+        let (Some(left), Some(right)) = (
+            assignment.child_by_field_name("left"),
+            assignment.child_by_field_name("right"),
+        ) else {
+            return;
+        };
+
+        let target = node_text(&left, source);
+        if target == "module.exports" {
+            match right.kind() {
+                "identifier" => {
+                    self.names.insert(node_text(&right, source));
+                }
+                "object" => {
+                    let mut cursor = right.walk();
+                    for property in right.named_children(&mut cursor) {
+                        match property.kind() {
+                            "shorthand_property_identifier" => {
+                                self.names.insert(node_text(&property, source));
+                            }
+                            "pair" => {
+                                if let Some(value) = property
+                                    .child_by_field_name("value")
+                                    .filter(|v| v.kind() == "identifier")
+                                {
+                                    self.names.insert(node_text(&value, source));
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        } else if target.starts_with("module.exports.") || target.starts_with("exports.") {
+            if let Some(property) = left.child_by_field_name("property") {
+                self.names.insert(node_text(&property, source));
+            }
+            if right.kind() == "identifier" {
+                self.names.insert(node_text(&right, source));
+            }
+        }
+    }
+
+    /// `__all__ = ["a", "b"]` and `__all__ += [...]`
+    fn collect_python_all(&mut self, node: &Node, source: &str) {
+        let Some(assignment) = node
+            .named_child(0)
+            .filter(|n| n.kind() == "assignment" || n.kind() == "augmented_assignment")
+        else {
+            return;
+        };
+        let is_all = assignment
+            .child_by_field_name("left")
+            .is_some_and(|left| node_text(&left, source) == "__all__");
+        let Some(right) = assignment.child_by_field_name("right").filter(|_| is_all) else {
+            return;
+        };
+
+        self.explicit = true;
+        let mut cursor = right.walk();
+        for item in right.named_children(&mut cursor).filter(|n| n.kind() == "string") {
+            let name = node_text(&item, source);
+            self.names.insert(name.trim_matches(|c| c == '"' || c == '\'').to_string());
+        }
+    }
+}
+
+/// Work out the visibility of a declaration and whether it is exported
+pub(crate) fn resolve_visibility(node: &Node, name: &str, ctx: &FileContext) -> (Visibility, bool) {
+    match ctx.language {
+        "typescript" | "javascript" | "tsx" | "jsx" => js_visibility(node, name, ctx),
+        "python" => python_visibility(node, name, ctx),
+        "rust" => rust_visibility(node, ctx),
+        "go" => go_visibility(name),
+        "java" => java_visibility(node, ctx),
+        "c" | "cpp" | "c++" => c_visibility(node, ctx),
+        _ => (Visibility::Public, false),
+    }
+}
+
+/// TS/JS: `export` wrappers, export clauses and CommonJS; members use
+/// `private`/`protected` modifiers or `#private` names
+fn js_visibility(node: &Node, name: &str, ctx: &FileContext) -> (Visibility, bool) {
+    if let Some(owner) = enclosing_type(node) {
+        let visibility = if name.starts_with('#') {
+            Visibility::Private
+        } else {
+            match modifier_text(node, "accessibility_modifier", ctx.source).as_deref() {
+                Some("private") => Visibility::Private,
+                Some("protected") => Visibility::Protected,
+                _ => Visibility::Public,
+            }
+        };
+        return (visibility, visibility == Visibility::Public && owner_exported(&owner, ctx));
+    }
+
+    let exported = has_export_wrapper(node) || (is_top_level(node) && ctx.exports.contains(name));
+    if exported {
+        (Visibility::Public, true)
+    } else {
+        (Visibility::Private, false)
+    }
+}
+
+/// Python: `__all__` when declared, otherwise the underscore convention
+fn python_visibility(node: &Node, name: &str, ctx: &FileContext) -> (Visibility, bool) {
+    let visibility = if name.starts_with("__") && name.ends_with("__") {
+        Visibility::Public
+    } else if name.starts_with("__") {
+        Visibility::Private
+    } else if name.starts_with('_') {
+        Visibility::Internal
+    } else {
+        Visibility::Public
+    };
+
+    let exported = if let Some(owner) = enclosing_type(node) {
+        visibility == Visibility::Public && owner_exported(&owner, ctx)
+    } else if !is_top_level(node) {
+        false
+    } else if ctx.exports.explicit {
+        ctx.exports.contains(name)
+    } else {
+        visibility == Visibility::Public
+    };
+
+    (visibility, exported)
+}
+
+/// Rust: `pub` is public, restricted `pub(...)` is crate-internal; trait
+/// items and trait impl methods are as visible as the trait
+fn rust_visibility(node: &Node, ctx: &FileContext) -> (Visibility, bool) {
+    let in_trait = enclosing_type(node).is_some_and(|owner| {
+        owner.kind() == "trait_item" || owner.child_by_field_name("trait").is_some()
+    });
+
+    let visibility = match modifier_text(node, "visibility_modifier", ctx.source).as_deref() {
+        Some("pub") => Visibility::Public,
+        Some("pub(self)") => Visibility::Private,
+        Some(_) => Visibility::Internal,
+        None if in_trait => Visibility::Public,
+        None => Visibility::Private,
+    };
+
+    (visibility, visibility == Visibility::Public)
+}
+
+/// Go: capitalised identifiers are exported from the package
+fn go_visibility(name: &str) -> (Visibility, bool) {
+    if name.starts_with(|c: char| c.is_uppercase()) {
+        (Visibility::Public, true)
+    } else {
+        (Visibility::Internal, false)
+    }
+}
+
+/// Java: access modifiers, package-private by default (public in interfaces)
+fn java_visibility(node: &Node, ctx: &FileContext) -> (Visibility, bool) {
+    let owner = enclosing_type(node);
+
+    let mut visibility = if owner.is_some_and(|o| o.kind() == "interface_declaration") {
+        Visibility::Public
+    } else {
+        Visibility::Internal
+    };
+
+    let mut cursor = node.walk();
+    if let Some(modifiers) = node.children(&mut cursor).find(|n| n.kind() == "modifiers") {
+        let mut modifier_cursor = modifiers.walk();
+        for modifier in modifiers.children(&mut modifier_cursor) {
+            match modifier.kind() {
+                "public" => visibility = Visibility::Public,
+                "protected" => visibility = Visibility::Protected,
+                "private" => visibility = Visibility::Private,
+                _ => {}
+            }
+        }
+    }
+
+    let visible = matches!(visibility, Visibility::Public | Visibility::Protected);
+    let exported = match owner {
+        Some(owner) => visible && owner_exported(&owner, ctx),
+        None => visible,
+    };
+
+    (visibility, exported)
+}
+
+/// C/C++: `static` gives internal linkage; members follow the nearest
+/// access specifier (private by default in classes, public in structs)
+fn c_visibility(node: &Node, ctx: &FileContext) -> (Visibility, bool) {
+    if let Some(owner) = enclosing_type(node) {
+        let mut visibility = if owner.kind() == "class_specifier" {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        };
+
+        let mut sibling = node.prev_sibling();
+        while let Some(current) = sibling {
+            if current.kind() == "access_specifier" {
+                visibility = match node_text(&current, ctx.source).as_str() {
+                    "private" => Visibility::Private,
+                    "protected" => Visibility::Protected,
+                    _ => Visibility::Public,
+                };
+                break;
+            }
+            sibling = current.prev_sibling();
+        }
+
+        return (visibility, visibility == Visibility::Public && owner_exported(&owner, ctx));
+    }
+
+    let mut cursor = node.walk();
+    let is_static = node
+        .children(&mut cursor)
+        .any(|n| n.kind() == "storage_class_specifier" && node_text(&n, ctx.source) == "static");
+
+    if is_static {
+        (Visibility::Private, false)
+    } else {
+        (Visibility::Public, true)
+    }
+}
+
+/// Whether the type that owns a member is itself exported
+fn owner_exported(owner: &Node, ctx: &FileContext) -> bool {
+    // Rust impl blocks take their visibility from the methods themselves
+    if owner.kind() == "impl_item" {
+        return true;
+    }
+
+    let name = owner
+        .child_by_field_name("name")
+        .map(|n| node_text(&n, ctx.source))
+        .unwrap_or_default();
+
+    resolve_visibility(owner, &name, ctx).1
+}
+
+/// The type a member is declared directly in, if any
+pub(crate) fn enclosing_type<'tree>(node: &Node<'tree>) -> Option<Node<'tree>> {
+    let mut parent = node.parent()?;
+    while matches!(parent.kind(), "decorated_definition" | "template_declaration") {
+        parent = parent.parent()?;
+    }
+
+    if !MEMBER_BODIES.contains(&parent.kind()) {
+        return None;
+    }
+
+    parent.parent().filter(|owner| TYPE_DECLARATIONS.contains(&owner.kind()))
+}
+
+/// Whether a declaration sits inside an `export` statement
+fn has_export_wrapper(node: &Node) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "export_statement" {
+            return true;
+        }
+        if !DECLARATION_WRAPPERS.contains(&parent.kind()) {
+            return false;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether a declaration is at module level
+pub(crate) fn is_top_level(node: &Node) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if ROOT_NODES.contains(&parent.kind()) {
+            return true;
+        }
+        if !DECLARATION_WRAPPERS.contains(&parent.kind()) {
+            return false;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Text of the first child of the given kind (modifier keywords)
+fn modifier_text(node: &Node, kind: &str, source: &str) -> Option<String> {
+    let mut cursor = node.walk();
+    let modifier = node.children(&mut cursor).find(|n| n.kind() == kind);
+    modifier.map(|n| node_text(&n, source))
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{CallSite, FunctionInfo, PrismParser};
+
+    fn parse_functions(code: &str, language: &str) -> Vec<FunctionInfo> {
+        let mut parser = PrismParser::new(language).unwrap();
+        parser.parse(code).unwrap().functions
+    }
+
+    fn summary(calls: &[CallSite]) -> Vec<(&str, &str, Option<&str>, bool)> {
+        calls
+            .iter()
+            .map(|c| (c.callee.as_str(), c.name.as_str(), c.receiver.as_deref(), c.is_awaited))
+            .collect()
+    }
+
+    fn calls_of<'a>(functions: &'a [FunctionInfo], name: &str) -> &'a [CallSite] {
+        &functions.iter().find(|f| f.name == name).unwrap().calls
+    }
+
+    #[test]
+    fn test_typescript_call_sites() {
+        let code = r#"
+async function load(id: string) {
+    const user = await this.store.get(id);
+    const names = user.items.map(item => format(item));
+    function helper() {
+        return validate(user);
+    }
+    return helper();
+}
+"#;
+
+        let functions = parse_functions(code, "typescript");
+        let load = calls_of(&functions, "load");
+        assert_eq!(
+            summary(load),
+            vec![
+                ("this.store.get", "get", Some("this.store"), true),
+                ("user.items.map", "map", Some("user.items"), false),
+                ("format", "format", None, false),
+                ("helper", "helper", None, false),
+            ]
+        );
+        assert_eq!(load[0].line, 3);
+
+        // Named nested functions keep their own calls
+        assert_eq!(summary(calls_of(&functions, "helper")), vec![("validate", "validate", None, false)]);
+    }
+
+    #[test]
+    fn test_call_sites_across_languages() {
+        let rust = parse_functions(
+            "async fn run(client: Client) {\n    let v = Vec::new();\n    client.send(v).await;\n    parse::<u8>(\"1\");\n}\n",
+            "rust",
+        );
+        assert_eq!(
+            summary(calls_of(&rust, "run")),
+            vec![
+                ("Vec::new", "new", Some("Vec"), false),
+                ("client.send", "send", Some("client"), true),
+                ("parse::<u8>", "parse", None, false),
+            ]
+        );
+
+        let python = parse_functions("def main():\n    data = json.loads(raw)\n    print(data)\n", "python");
+        assert_eq!(
+            summary(calls_of(&python, "main")),
+            vec![("json.loads", "loads", Some("json"), false), ("print", "print", None, false)]
+        );
+
+        let go = parse_functions("package main\n\nfunc main() {\n\tfmt.Println(run())\n}\n", "go");
+        assert_eq!(
+            summary(calls_of(&go, "main")),
+            vec![("fmt.Println", "Println", Some("fmt"), false), ("run", "run", None, false)]
+        );
+
+        let java = parse_functions("class A {\n    void f() {\n        this.list.add(compute());\n    }\n}\n", "java");
+        assert_eq!(
+            summary(calls_of(&java, "f")),
+            vec![("this.list.add", "add", Some("this.list"), false), ("compute", "compute", None, false)]
+        );
+    }
+
+    #[test]
+    fn test_chunk_calls() {
+        let code = "def first():\n    alpha()\n    beta()\n\n\ndef second():\n    gamma()\n";
+        let mut parser = PrismParser::new("python").unwrap();
+        let result = parser.parse(code).unwrap();
+
+        let chunk = result.chunks.iter().find(|c| c.text.contains("def second")).unwrap();
+        let names: Vec<&str> = chunk.calls.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["gamma"]);
+    }
+}
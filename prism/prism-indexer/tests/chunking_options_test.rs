@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{ChunkingOptions, CodeChunk, PrismError, PrismParser, MAX_CHUNK_SIZE};
+
+    fn chunks(code: &str, language: &str, options: &ChunkingOptions) -> Vec<CodeChunk> {
+        let mut parser = PrismParser::new(language).unwrap();
+        parser.set_chunking_options(options).unwrap();
+        parser.parse(code).unwrap().chunks
+    }
+
+    fn invalid(options: ChunkingOptions) -> String {
+        let mut parser = PrismParser::new("python").unwrap();
+        match parser.set_chunking_options(&options) {
+            Err(PrismError::InvalidOptions(message)) => message,
+            other => panic!("expected invalid options, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    const SOURCE: &str = r#"import os
+from pathlib import Path
+
+def load(path):
+    """Read a file from disk."""
+    return Path(path).read_text()
+
+def save(path, text):
+    Path(path).write_text(text)
+"#;
+
+    #[test]
+    fn test_invalid_options_are_rejected() {
+        assert_eq!(
+            invalid(ChunkingOptions { target_tokens: Some(0), ..Default::default() }),
+            "target_tokens must be greater than 0"
+        );
+        assert_eq!(
+            invalid(ChunkingOptions { target_tokens: Some(400), max_tokens: Some(300), ..Default::default() }),
+            "max_tokens (300) must be at least target_tokens (400)"
+        );
+        assert_eq!(
+            invalid(ChunkingOptions { overlap_tokens: Some(512), ..Default::default() }),
+            "overlap_tokens (512) must be less than target_tokens (512)"
+        );
+        assert_eq!(
+            invalid(ChunkingOptions { min_lines: Some(20), max_lines: Some(10), ..Default::default() }),
+            "min_lines (20) must not exceed max_lines (10)"
+        );
+
+        // A rejected update keeps the previous options
+        let mut parser = PrismParser::new("python").unwrap();
+        assert!(parser.set_chunking_options(&ChunkingOptions { max_lines: Some(0), ..Default::default() }).is_err());
+        assert!(!parser.parse(SOURCE).unwrap().chunks[0].imports.is_empty());
+    }
+
+    #[test]
+    fn test_imports_and_docs_can_be_left_out() {
+        let defaults = chunks(SOURCE, "python", &ChunkingOptions::default());
+        assert!(defaults.iter().all(|c| c.imports.len() == 2));
+
+        let options = ChunkingOptions {
+            include_imports: Some(false),
+            include_docs: Some(false),
+            ..Default::default()
+        };
+        let bare = chunks(SOURCE, "python", &options);
+        assert!(bare.iter().all(|c| c.imports.is_empty()));
+
+        // Python docstrings sit inside the function, so the text is unchanged
+        let load = bare.iter().find(|c| c.qualified_name.as_deref() == Some("load")).unwrap();
+        assert!(load.text.starts_with("def load(path):"));
+    }
+
+    #[test]
+    fn test_max_tokens_splits_large_chunks() {
+        let mut code = String::from("function handler(event) {\n");
+        for i in 0..60 {
+            code.push_str(&format!("  const value{} = event.payload.items[{}].amount * rate;\n", i, i));
+        }
+        code.push_str("  return event;\n}\n");
+
+        let whole = chunks(&code, "javascript", &ChunkingOptions::default());
+        assert_eq!(whole.len(), 1);
+        assert!(whole[0].tokens <= MAX_CHUNK_SIZE);
+
+        let options = ChunkingOptions {
+            target_tokens: Some(200),
+            max_tokens: Some(300),
+            ..Default::default()
+        };
+        let pieces = chunks(&code, "javascript", &options);
+        assert!(pieces.len() > 1);
+        assert!(pieces.iter().all(|c| c.tokens <= 200));
+        assert_eq!(pieces.first().unwrap().start_line, 1);
+        assert_eq!(pieces.last().unwrap().end_line, whole[0].end_line);
+
+        // Each piece only defines the names on its own lines
+        let defines = |chunk: &CodeChunk, name: &str| chunk.defines.iter().any(|c| c.name == name);
+        assert!(defines(&pieces[0], "value0") && !defines(&pieces[0], "value59"));
+        assert!(defines(pieces.last().unwrap(), "value59") && !defines(pieces.last().unwrap(), "value0"));
+    }
+
+    #[test]
+    fn test_oversized_chunks_are_split_by_default() {
+        let mut code = String::from("def handler(event):\n");
+        for i in 0..200 {
+            code.push_str(&format!("    value{} = event.payload.items[{}].amount * rate\n", i, i));
+        }
+        code.push_str("    return event\n");
+
+        let whole = chunks(&code, "python", &ChunkingOptions { max_tokens: Some(10_000), ..Default::default() });
+        assert_eq!(whole.len(), 1);
+        assert!(whole[0].tokens > MAX_CHUNK_SIZE);
+
+        // Without max_tokens, the function is still split below the size limit
+        let pieces = chunks(&code, "python", &ChunkingOptions::default());
+        assert!(pieces.len() > 1);
+        assert!(pieces.iter().all(|c| c.tokens <= MAX_CHUNK_SIZE));
+        assert_eq!(pieces.first().unwrap().start_line, 1);
+        assert_eq!(pieces.last().unwrap().end_line, 202);
+    }
+}
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
-    use prism_indexer::{chunk_code, split_large_chunk, CodeChunk};
+    use prism_indexer::{
+        chunk_code, chunk_code_with_options, split_large_chunk, ChunkingOptions, CodeChunk, MAX_CHUNK_SIZE,
+    };
     use tree_sitter::Parser;
 
     /// Helper function to create a parser for a language
@@ -8,11 +10,14 @@ mod tests {
         let mut parser = Parser::new();
         let language_obj = match language {
             "typescript" => tree_sitter_typescript::language_typescript(),
+            "tsx" => tree_sitter_typescript::language_tsx(),
             "javascript" => tree_sitter_javascript::language_javascript(),
             "python" => tree_sitter_python::language_python(),
             "rust" => tree_sitter_rust::language_rust(),
             "go" => tree_sitter_go::language_go(),
             "java" => tree_sitter_java::language_java(),
+            "c" => tree_sitter_c::LANGUAGE.into(),
+            "cpp" => tree_sitter_cpp::LANGUAGE.into(),
             _ => panic!("Unsupported language: {}", language),
         };
         parser.set_language(&language_obj).unwrap();
@@ -122,12 +127,13 @@ export class Calculator {
         // Should have at least one chunk
         assert!(!chunks.is_empty());
 
-        // If chunk is too large, verify it can be split
-        let large_chunk = chunks.iter().find(|c| c.tokens > 512);
-        if let Some(chunk) = large_chunk {
-            let split_chunks = split_large_chunk(chunk, 512);
-            assert!(split_chunks.len() > 1, "Large chunk should be split");
-        }
+        // Chunks over the size limit are split by default
+        assert!(chunks.len() > 1, "Large chunk should be split");
+        assert!(chunks.iter().all(|c| c.tokens <= MAX_CHUNK_SIZE));
+
+        // Pieces can be split further
+        let split_chunks = split_large_chunk(&chunks[0], 128);
+        assert!(split_chunks.len() > 1);
     }
 
     #[test]
@@ -323,4 +329,139 @@ export function broken(
             assert!(split_chunks.len() > 1, "Large class should be split");
         }
     }
+
+    #[test]
+    fn test_cpp_chunking() {
+        let code = r#"
+#include <vector>
+#include "shape.h"
+
+namespace geometry {
+
+class Circle : public Shape {
+public:
+    double area() const { return 3.14 * r * r; }
+    virtual void draw() const;
+private:
+    double r;
+};
+
+int *Circle::scale(int factor) {
+    return nullptr;
+}
+
+}
+"#;
+
+        let mut parser = create_parser("cpp");
+        let tree = parser.parse(code, None).unwrap();
+        let chunks = chunk_code(&tree.root_node(), code, "cpp");
+
+        // Should find the class with inline and declared methods
+        let class_chunk = chunks.iter().find(|c| c.text.contains("class Circle")).unwrap();
+        assert_eq!(class_chunk.classes[0].name, "Circle");
+        let methods: Vec<&str> = class_chunk.classes[0].methods.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(methods, vec!["area", "draw"]);
+
+        // Out-of-line definitions are named through nested declarators
+        let func_chunk = chunks.iter().find(|c| c.text.contains("Circle::scale")).unwrap();
+        assert_eq!(func_chunk.functions[0].name, "scale");
+
+        // Includes are recorded as imports
+        let sources: Vec<&str> = chunks[0].imports.iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(sources, vec!["vector", "shape.h"]);
+    }
+
+    #[test]
+    fn test_c_preprocessor_blocks() {
+        let code = r#"
+#include <stdio.h>
+
+typedef struct {
+    int x;
+    int y;
+} Point;
+
+#ifdef DEBUG
+static void trace(const char *msg) {
+    fprintf(stderr, "%s\n", msg);
+}
+#endif
+"#;
+
+        let mut parser = create_parser("c");
+        let tree = parser.parse(code, None).unwrap();
+        let chunks = chunk_code(&tree.root_node(), code, "c");
+
+        // Typedef'd anonymous structs take the typedef name
+        let struct_chunk = chunks.iter().find(|c| !c.classes.is_empty()).unwrap();
+        assert_eq!(struct_chunk.classes[0].name, "Point");
+
+        // The guarded function keeps its #ifdef/#endif lines
+        let func_chunk = chunks.iter().find(|c| !c.functions.is_empty()).unwrap();
+        assert_eq!(func_chunk.functions[0].name, "trace");
+        assert!(func_chunk.text.starts_with("#ifdef DEBUG"));
+        assert!(func_chunk.text.ends_with("#endif"));
+    }
+
+    #[test]
+    fn test_c_header_split_keeps_preprocessor_blocks() {
+        let mut code = String::from("#ifndef BIG_H\n#define BIG_H\n");
+        for i in 0..190 {
+            code.push_str(&format!("int proto_{}(int value);\n", i));
+        }
+        code.push_str("#if defined(_WIN32)\n");
+        for i in 0..20 {
+            code.push_str(&format!("int win_{}(void);\n", i));
+        }
+        code.push_str("#endif\n#endif\n");
+
+        // Only the line limit splits the header, not its token count
+        let options = ChunkingOptions { max_tokens: Some(10_000), ..Default::default() };
+        let mut parser = create_parser("c");
+        let tree = parser.parse(&code, None).unwrap();
+        let chunks = chunk_code_with_options(&tree.root_node(), &code, "c", &options).unwrap();
+
+        // The header is split before the platform block, not inside it; the
+        // second piece repeats the prototypes just before the block
+        assert_eq!(chunks.len(), 2);
+        let overlap = chunks[1].overlap.unwrap();
+        assert_eq!(overlap.end_line, chunks[0].end_line);
+        let repeated = overlap.end_line - overlap.start_line + 1;
+        assert_eq!(chunks[1].text.lines().nth(repeated), Some("#if defined(_WIN32)"));
+    }
+
+    #[test]
+    fn test_tsx_component_chunking() {
+        let code = r#"
+import React from 'react';
+
+export function UserList({ users }: Props) {
+  if (users.length === 0) {
+    return <Empty />;
+  }
+  return (
+    <ul>
+      {users.map(u => <li key={u.id}>{u.name}</li>)}
+    </ul>
+  );
+}
+
+function formatName(user: User): string {
+  return `${user.first} ${user.last}`;
+}
+"#;
+
+        let mut parser = create_parser("tsx");
+        let tree = parser.parse(code, None).unwrap();
+        assert!(!tree.root_node().has_error());
+
+        let chunks = chunk_code(&tree.root_node(), code, "tsx");
+
+        let component = chunks.iter().find(|c| c.text.contains("UserList")).unwrap();
+        assert!(component.functions[0].is_component);
+
+        let helper = chunks.iter().find(|c| c.text.contains("formatName")).unwrap();
+        assert!(!helper.functions[0].is_component);
+    }
 }
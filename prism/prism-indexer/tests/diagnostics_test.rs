@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{ErrorNode, PrismParser, Severity, MAX_DIAGNOSTICS};
+
+    fn diagnostics(code: &str, language: &str) -> Vec<ErrorNode> {
+        let mut parser = PrismParser::new(language).unwrap();
+        parser.parse(code).unwrap().error_nodes
+    }
+
+    #[test]
+    fn test_missing_tokens() {
+        let errors = diagnostics("int main() {\n  int x = 1\n  return x;\n}\n", "c");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Missing \";\" in declaration");
+        assert_eq!(errors[0].missing.as_deref(), Some(";"));
+        assert_eq!(errors[0].parent_kind.as_deref(), Some("declaration"));
+        assert_eq!(errors[0].severity, Severity::Warning);
+
+        let errors = diagnostics("function f(a {\n  return a\n}\n", "typescript");
+        assert_eq!(errors[0].message, "Missing \")\" in formal parameters");
+        assert_eq!(errors[0].severity, Severity::Error);
+        assert_eq!((errors[0].location.start_row, errors[0].location.start_column), (0, 12));
+    }
+
+    #[test]
+    fn test_unexpected_input() {
+        let errors = diagnostics("class A {\n  m() { let x = ; }\n}\n", "typescript");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.starts_with("Unexpected \"=\" in lexical declaration; expected"));
+        assert!(errors[0].expected.contains(&"\";\"".to_string()));
+        assert!(!errors[0].expected.iter().any(|token| token.contains("comment")));
+    }
+
+    #[test]
+    fn test_snippets_nesting_and_cap() {
+        let long_line = format!("const y = [1, {}\n", "2, ".repeat(100));
+        let errors = diagnostics(&long_line, "typescript");
+        assert!(errors[0].text.ends_with('…'));
+        assert!(errors[0].text.chars().count() <= 61);
+
+        let code = "def f(:\n    x = (1 +\n".repeat(50);
+        let errors = diagnostics(&code, "python");
+        assert!(!errors.is_empty() && errors.len() <= MAX_DIAGNOSTICS);
+
+        // Errors inside a reported error are not reported again
+        let contains = |outer: &ErrorNode, inner: &ErrorNode| {
+            (outer.location.start_row, outer.location.start_column)
+                <= (inner.location.start_row, inner.location.start_column)
+                && (outer.location.end_row, outer.location.end_column)
+                    >= (inner.location.end_row, inner.location.end_column)
+        };
+        for (i, outer) in errors.iter().enumerate() {
+            assert!(errors
+                .iter()
+                .enumerate()
+                .all(|(j, inner)| i == j || inner.missing.is_some() || !contains(outer, inner)));
+        }
+    }
+}
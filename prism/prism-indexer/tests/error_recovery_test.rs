@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{CodeChunk, PrismParser, SymbolKind};
+
+    fn chunks(code: &str, language: &str) -> Vec<CodeChunk> {
+        let mut parser = PrismParser::new(language).unwrap();
+        let mut chunks = parser.parse(code).unwrap().chunks;
+        chunks.sort_by_key(|c| c.start_line);
+        chunks
+    }
+
+    fn spans(chunks: &[CodeChunk]) -> Vec<(usize, usize, bool)> {
+        chunks.iter().map(|c| (c.start_line, c.end_line, c.has_errors)).collect()
+    }
+
+    #[test]
+    fn test_recovers_declarations_lost_to_errors() {
+        let code = r#"import { a } from './a';
+
+export function first(x: number) {
+  const items = [1, 2, 3;
+  return a(x);
+}
+
+export function second(y: number) {
+  const z = y * 2;
+  return z + 1;
+}
+
+function third( {
+  return 3;
+}
+
+function tail() {
+  return 'ok';
+}
+"#;
+        let result = chunks(code, "typescript");
+        assert_eq!(
+            spans(&result),
+            vec![(3, 6, true), (8, 11, false), (13, 15, true), (17, 19, true)]
+        );
+
+        // `third` only survives through the brace heuristics
+        let third = &result[2];
+        assert_eq!(third.kind, Some(SymbolKind::Function));
+        assert!(third.text.starts_with("function third("));
+        assert!(third.functions.is_empty());
+    }
+
+    #[test]
+    fn test_splits_definitions_that_swallowed_code() {
+        let code = r#"import os
+
+def first(x):
+    return os.path.join(x,
+
+def second(y):
+    z = y * 2
+    return z + 1
+
+@decorated
+class Thing:
+    def method(self):
+        return 1
+
+def tail():
+    return 'ok'
+"#;
+        let result = chunks(code, "python");
+        let summary: Vec<(usize, usize, Option<SymbolKind>)> =
+            result.iter().map(|c| (c.start_line, c.end_line, c.kind)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, 2, None),
+                (3, 4, Some(SymbolKind::Function)),
+                (6, 8, Some(SymbolKind::Function)),
+                (10, 13, Some(SymbolKind::Class)),
+                (15, 16, Some(SymbolKind::Function)),
+            ]
+        );
+        assert!(result.iter().all(|c| c.has_errors));
+
+        // The parsed definition no longer claims the lines it swallowed
+        assert_eq!(result[1].functions[0].end_line, 4);
+    }
+
+    #[test]
+    fn test_clean_files_are_unaffected() {
+        let code = "fn main() {\n    println!(\"hi\");\n}\n\nfn helper() -> u32 {\n    1\n}\n";
+        let result = chunks(code, "rust");
+        assert_eq!(spans(&result), vec![(1, 3, false), (5, 7, false)]);
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{BindingKind, ExportInfo, PrismParser};
+
+    fn exports(code: &str, language: &str) -> Vec<ExportInfo> {
+        let mut parser = PrismParser::new(language).unwrap();
+        parser.parse(code).unwrap().exports
+    }
+
+    fn summary(exports: &[ExportInfo]) -> Vec<(&str, Option<&str>, Option<&str>, BindingKind)> {
+        exports
+            .iter()
+            .map(|e| (e.name.as_str(), e.local.as_deref(), e.source.as_deref(), e.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_es_module_exports() {
+        let code = r#"
+export function parse() {}
+export default function render() {}
+export const a = 1, b = 2;
+export interface Props {}
+export { a as alias, b as default };
+export * from './barrel';
+export * as utils from './utils';
+export { default as Button, type Theme } from './button';
+export type { Config } from './config';
+"#;
+
+        let result = exports(code, "typescript");
+        assert_eq!(
+            summary(&result),
+            vec![
+                ("parse", Some("parse"), None, BindingKind::Named),
+                ("default", Some("render"), None, BindingKind::Default),
+                ("a", Some("a"), None, BindingKind::Named),
+                ("b", Some("b"), None, BindingKind::Named),
+                ("Props", Some("Props"), None, BindingKind::Named),
+                ("alias", Some("a"), None, BindingKind::Named),
+                ("default", Some("b"), None, BindingKind::Default),
+                ("*", None, Some("./barrel"), BindingKind::Wildcard),
+                ("utils", None, Some("./utils"), BindingKind::Namespace),
+                ("Button", Some("default"), Some("./button"), BindingKind::Named),
+                ("Theme", Some("Theme"), Some("./button"), BindingKind::Named),
+                ("Config", Some("Config"), Some("./config"), BindingKind::Named),
+            ]
+        );
+
+        let type_only: Vec<&str> = result.iter().filter(|e| e.is_type_only).map(|e| e.name.as_str()).collect();
+        assert_eq!(type_only, vec!["Props", "Theme", "Config"]);
+
+        let anonymous = exports("export default class {}\n", "typescript");
+        assert_eq!(summary(&anonymous), vec![("default", None, None, BindingKind::Default)]);
+    }
+
+    #[test]
+    fn test_commonjs_exports() {
+        let code = r#"
+function helper() {}
+module.exports = { helper, run: start };
+exports.version = VERSION;
+"#;
+
+        let result = exports(code, "javascript");
+        assert_eq!(
+            summary(&result),
+            vec![
+                ("helper", Some("helper"), None, BindingKind::Named),
+                ("run", Some("start"), None, BindingKind::Named),
+                ("version", Some("VERSION"), None, BindingKind::Named),
+            ]
+        );
+
+        let barrel = exports("module.exports = require('./lib');\n", "javascript");
+        assert_eq!(summary(&barrel), vec![("*", None, Some("./lib"), BindingKind::Wildcard)]);
+    }
+
+    #[test]
+    fn test_rust_and_python_reexports() {
+        let code = r#"
+pub use crate::parser::{PrismParser, Options as ParseOptions};
+pub use crate::types::*;
+pub(crate) use crate::internal::Helper;
+use std::io;
+"#;
+
+        let result = exports(code, "rust");
+        assert_eq!(
+            summary(&result),
+            vec![
+                ("PrismParser", Some("PrismParser"), Some("crate::parser"), BindingKind::Named),
+                ("ParseOptions", Some("Options"), Some("crate::parser"), BindingKind::Named),
+                ("*", None, Some("crate::types"), BindingKind::Wildcard),
+            ]
+        );
+
+        let code = "from .models import User as Account\n\ndef helper():\n    pass\n\n__all__ = ['Account', 'helper']\n";
+        let result = exports(code, "python");
+        assert_eq!(
+            summary(&result),
+            vec![
+                ("Account", Some("User"), Some(".models"), BindingKind::Named),
+                ("helper", Some("helper"), None, BindingKind::Named),
+            ]
+        );
+    }
+}
@@ -0,0 +1,629 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{ClassInfo, FunctionInfo, ParseResult, PrismParser, SymbolInfo, SymbolKind, Visibility};
+
+    /// Helper function to parse code with the public parser
+    fn parse(code: &str, language: &str) -> ParseResult {
+        let mut parser = PrismParser::new(language).unwrap();
+        parser.parse(code).unwrap()
+    }
+
+    /// Helper function to find an extracted class by name
+    fn find_class<'a>(result: &'a ParseResult, name: &str) -> &'a ClassInfo {
+        result
+            .classes
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("Class {} not found", name))
+    }
+
+    /// Helper function to find an extracted function by name
+    fn find_function<'a>(result: &'a ParseResult, name: &str) -> &'a FunctionInfo {
+        result
+            .functions
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("Function {} not found", name))
+    }
+
+    fn flatten(symbols: &[SymbolInfo]) -> Vec<&SymbolInfo> {
+        symbols
+            .iter()
+            .flat_map(|s| std::iter::once(s).chain(flatten(&s.children)))
+            .collect()
+    }
+
+    fn find_symbol<'a>(result: &'a ParseResult, qualified_name: &str) -> &'a SymbolInfo {
+        flatten(&result.symbols)
+            .into_iter()
+            .find(|s| s.qualified_name == qualified_name)
+            .unwrap_or_else(|| panic!("Symbol {} not found", qualified_name))
+    }
+
+    #[test]
+    fn test_typescript_parameters() {
+        let code = r#"
+function request(url: string, retries = 3, timeout?: number, ...headers: string[]): Promise<Response> {
+  return fetch(url);
+}
+"#;
+
+        let result = parse(code, "typescript");
+        let func = find_function(&result, "request");
+
+        assert_eq!(func.return_type.as_deref(), Some("Promise<Response>"));
+        assert_eq!(func.parameters.len(), 4);
+
+        let url = &func.parameters[0];
+        assert_eq!(url.name, "url");
+        assert_eq!(url.type_annotation.as_deref(), Some("string"));
+        assert!(!url.is_optional);
+
+        let retries = &func.parameters[1];
+        assert_eq!(retries.default_value.as_deref(), Some("3"));
+        assert!(retries.is_optional);
+
+        assert!(func.parameters[2].is_optional);
+
+        let headers = &func.parameters[3];
+        assert_eq!(headers.name, "headers");
+        assert_eq!(headers.type_annotation.as_deref(), Some("string[]"));
+        assert!(headers.is_variadic);
+    }
+
+    #[test]
+    fn test_python_parameters() {
+        let code = r#"
+def connect(host: str, port=5432, *args, timeout: float = 1.5, **options) -> Connection:
+    pass
+"#;
+
+        let result = parse(code, "python");
+        let func = find_function(&result, "connect");
+
+        assert_eq!(func.return_type.as_deref(), Some("Connection"));
+        let names: Vec<&str> = func.parameters.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["host", "port", "args", "timeout", "options"]);
+
+        assert_eq!(func.parameters[0].type_annotation.as_deref(), Some("str"));
+        assert_eq!(func.parameters[1].default_value.as_deref(), Some("5432"));
+        assert!(func.parameters[2].is_variadic);
+        assert_eq!(func.parameters[3].type_annotation.as_deref(), Some("float"));
+        assert_eq!(func.parameters[3].default_value.as_deref(), Some("1.5"));
+        assert!(func.parameters[4].is_variadic);
+    }
+
+    #[test]
+    fn test_rust_go_java_parameters() {
+        let rust = parse("fn get<'a>(&self, key: &'a str) -> Option<&'a str> { None }", "rust");
+        let func = find_function(&rust, "get");
+        assert_eq!(func.parameters[0].name, "&self");
+        assert_eq!(func.parameters[1].name, "key");
+        assert_eq!(func.parameters[1].type_annotation.as_deref(), Some("&'a str"));
+        assert_eq!(func.return_type.as_deref(), Some("Option<&'a str>"));
+
+        let go = parse(
+            "package main\nfunc Join(a, b string, rest ...string) (string, error) { return a, nil }\n",
+            "go",
+        );
+        let func = find_function(&go, "Join");
+        let names: Vec<&str> = func.parameters.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "rest"]);
+        assert_eq!(func.parameters[1].type_annotation.as_deref(), Some("string"));
+        assert!(func.parameters[2].is_variadic);
+        assert_eq!(func.return_type.as_deref(), Some("(string, error)"));
+
+        let java = parse(
+            "class Log { public String format(int level, Object... args) { return null; } }",
+            "java",
+        );
+        let func = find_function(&java, "format");
+        assert_eq!(func.return_type.as_deref(), Some("String"));
+        assert_eq!(func.parameters[0].type_annotation.as_deref(), Some("int"));
+        assert_eq!(func.parameters[1].name, "args");
+        assert_eq!(func.parameters[1].type_annotation.as_deref(), Some("Object"));
+        assert!(func.parameters[1].is_variadic);
+    }
+
+    #[test]
+    fn test_cpp_parameters() {
+        let code = "const char *label(const Widget &widget, int width = 80, ...) { return nullptr; }";
+
+        let result = parse(code, "cpp");
+        let func = find_function(&result, "label");
+
+        assert_eq!(func.return_type.as_deref(), Some("const char *"));
+        assert_eq!(func.parameters[0].name, "widget");
+        assert_eq!(func.parameters[0].type_annotation.as_deref(), Some("const Widget &"));
+        assert_eq!(func.parameters[1].default_value.as_deref(), Some("80"));
+        assert!(func.parameters[1].is_optional);
+        assert!(func.parameters[2].is_variadic);
+    }
+
+    #[test]
+    fn test_typescript_exports() {
+        let code = r#"
+export function publicApi() {}
+function helper() {}
+function reexported() {}
+export { reexported };
+
+export class Service {
+  run() {}
+  protected retry() {}
+  private reset() {}
+}
+"#;
+
+        let result = parse(code, "typescript");
+        assert!(find_function(&result, "publicApi").is_exported);
+        assert!(!find_function(&result, "helper").is_exported);
+        assert_eq!(find_function(&result, "helper").visibility, Visibility::Private);
+        assert!(find_function(&result, "reexported").is_exported);
+
+        let service = &result.classes[0];
+        assert!(service.is_exported);
+        assert!(service.methods[0].is_exported);
+        assert_eq!(service.methods[1].visibility, Visibility::Protected);
+        assert!(!service.methods[1].is_exported);
+        assert_eq!(service.methods[2].visibility, Visibility::Private);
+    }
+
+    #[test]
+    fn test_commonjs_exports() {
+        let code = r#"
+function start() {}
+function stop() {}
+function internal() {}
+module.exports = { start, halt: stop };
+"#;
+
+        let result = parse(code, "javascript");
+        assert!(find_function(&result, "start").is_exported);
+        assert!(find_function(&result, "stop").is_exported);
+        assert!(!find_function(&result, "internal").is_exported);
+    }
+
+    #[test]
+    fn test_rust_go_java_visibility() {
+        let rust = parse("pub fn api() {}\npub(crate) fn shared() {}\nfn local() {}\n", "rust");
+        assert_eq!(find_function(&rust, "api").visibility, Visibility::Public);
+        assert!(find_function(&rust, "api").is_exported);
+        assert_eq!(find_function(&rust, "shared").visibility, Visibility::Internal);
+        assert!(!find_function(&rust, "shared").is_exported);
+        assert_eq!(find_function(&rust, "local").visibility, Visibility::Private);
+
+        let go = parse("package main\nfunc Serve() {}\nfunc handle() {}\n", "go");
+        assert!(find_function(&go, "Serve").is_exported);
+        assert_eq!(find_function(&go, "handle").visibility, Visibility::Internal);
+
+        let java = parse(
+            "public class Api { public void get() {} protected void hook() {} void pkg() {} private void own() {} }",
+            "java",
+        );
+        let methods = &java.classes[0].methods;
+        assert!(methods[0].is_exported);
+        assert!(methods[1].is_exported);
+        assert_eq!(methods[2].visibility, Visibility::Internal);
+        assert!(!methods[2].is_exported);
+        assert_eq!(methods[3].visibility, Visibility::Private);
+    }
+
+    #[test]
+    fn test_python_visibility() {
+        let code = r#"
+__all__ = ["load"]
+
+def load():
+    pass
+
+def save():
+    pass
+
+def _cache():
+    pass
+"#;
+
+        let result = parse(code, "python");
+        assert!(find_function(&result, "load").is_exported);
+        assert!(!find_function(&result, "save").is_exported);
+        assert_eq!(find_function(&result, "save").visibility, Visibility::Public);
+        assert_eq!(find_function(&result, "_cache").visibility, Visibility::Internal);
+
+        // Without __all__, the underscore convention decides
+        let result = parse("def load():\n    pass\n\ndef _cache():\n    pass\n", "python");
+        assert!(find_function(&result, "load").is_exported);
+        assert!(!find_function(&result, "_cache").is_exported);
+    }
+
+    #[test]
+    fn test_typescript_java_heritage() {
+        let ts = parse(
+            "class Admin extends User<Role> implements Auditable, Comparable<Admin> {}\ninterface Store extends Reader, Writer {}\n",
+            "typescript",
+        );
+        let admin = find_class(&ts, "Admin");
+        assert_eq!(admin.extends.as_deref(), Some("User<Role>"));
+        assert_eq!(admin.implements, vec!["Auditable", "Comparable<Admin>"]);
+        assert_eq!(find_class(&ts, "Store").implements, vec!["Reader", "Writer"]);
+
+        let java = parse(
+            "class Admin extends User implements Auditable, Serializable {}\ninterface Store extends Reader {}\n",
+            "java",
+        );
+        let admin = find_class(&java, "Admin");
+        assert_eq!(admin.extends.as_deref(), Some("User"));
+        assert_eq!(admin.implements, vec!["Auditable", "Serializable"]);
+        assert_eq!(find_class(&java, "Store").implements, vec!["Reader"]);
+    }
+
+    #[test]
+    fn test_python_go_heritage() {
+        let python = parse(
+            "class Admin(User, mixins.Audit, metaclass=Meta):\n    pass\n\nclass Plain(object):\n    pass\n",
+            "python",
+        );
+        let admin = find_class(&python, "Admin");
+        assert_eq!(admin.extends.as_deref(), Some("User"));
+        assert_eq!(admin.implements, vec!["mixins.Audit"]);
+        assert_eq!(find_class(&python, "Plain").extends, None);
+
+        let go = parse(
+            "package store\ntype Cache struct {\n\t*Base\n\tsync.Mutex\n\tsize int\n}\ntype ReadCloser interface {\n\tio.Reader\n\tClose() error\n}\n",
+            "go",
+        );
+        assert_eq!(find_class(&go, "Cache").implements, vec!["Base", "sync.Mutex"]);
+        let read_closer = find_class(&go, "ReadCloser");
+        assert_eq!(read_closer.implements, vec!["io.Reader"]);
+        assert_eq!(read_closer.methods[0].name, "Close");
+    }
+
+    #[test]
+    fn test_rust_trait_impls() {
+        let code = r#"
+pub trait Shape: Debug + Clone {
+    fn area(&self) -> f64;
+}
+
+pub struct Circle {
+    radius: f64,
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 { 3.14 * self.radius * self.radius }
+}
+
+impl fmt::Display for Circle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }
+}
+
+pub struct Marker;
+
+impl Shape for Marker {
+    fn area(&self) -> f64 { 0.0 }
+}
+"#;
+
+        let result = parse(code, "rust");
+        assert_eq!(find_class(&result, "Shape").implements, vec!["Debug", "Clone"]);
+        assert_eq!(find_class(&result, "Circle").implements, vec!["Shape", "fmt::Display"]);
+
+        // Unit structs have no body but keep their trait impls
+        let marker = find_class(&result, "Marker");
+        assert_eq!(marker.implements, vec!["Shape"]);
+        assert!(marker.methods.is_empty());
+    }
+
+    #[test]
+    fn test_symbol_kinds() {
+        let kinds = |result: &ParseResult| -> Vec<(String, SymbolKind)> {
+            flatten(&result.symbols).iter().map(|s| (s.name.clone(), s.kind)).collect()
+        };
+
+        let ts = parse(
+            "export type Id = string;\nenum Color { Red }\nnamespace Util {}\nexport const MAX = 3;\nconst double = (x: number) => x * 2;\nclass A { run() {} }\n",
+            "typescript",
+        );
+        assert_eq!(
+            kinds(&ts),
+            vec![
+                ("Id".to_string(), SymbolKind::TypeAlias),
+                ("Color".to_string(), SymbolKind::Enum),
+                ("Util".to_string(), SymbolKind::Module),
+                ("MAX".to_string(), SymbolKind::Constant),
+                ("double".to_string(), SymbolKind::Function),
+                ("A".to_string(), SymbolKind::Class),
+                ("run".to_string(), SymbolKind::Method),
+            ]
+        );
+        assert_eq!(find_class(&ts, "Color").kind, SymbolKind::Enum);
+
+        let rust = parse(
+            "pub struct P { x: u8 }\nenum E { A }\ntrait T {}\nconst N: u8 = 1;\nmod m {}\nimpl P { fn new() -> Self { P } }\nfn main() {}\n",
+            "rust",
+        );
+        assert_eq!(find_class(&rust, "P").kind, SymbolKind::Struct);
+        assert_eq!(find_class(&rust, "E").kind, SymbolKind::Enum);
+        assert_eq!(find_class(&rust, "T").kind, SymbolKind::Trait);
+        assert_eq!(find_function(&rust, "new").kind, SymbolKind::Method);
+        assert_eq!(find_function(&rust, "main").kind, SymbolKind::Function);
+        assert!(kinds(&rust).contains(&("N".to_string(), SymbolKind::Constant)));
+        assert!(kinds(&rust).contains(&("m".to_string(), SymbolKind::Module)));
+
+        let go = parse(
+            "package p\ntype S struct{}\ntype I interface{}\ntype ID = int\nconst Limit = 10\n",
+            "go",
+        );
+        assert_eq!(
+            kinds(&go),
+            vec![
+                ("S".to_string(), SymbolKind::Struct),
+                ("I".to_string(), SymbolKind::Interface),
+                ("ID".to_string(), SymbolKind::TypeAlias),
+                ("Limit".to_string(), SymbolKind::Constant),
+            ]
+        );
+
+        let java = parse(
+            "record Point(int x, int y) {}\nclass C {\n    static final int MAX = 1;\n    int count;\n}\n",
+            "java",
+        );
+        assert_eq!(find_class(&java, "Point").kind, SymbolKind::Class);
+        assert!(kinds(&java).contains(&("MAX".to_string(), SymbolKind::Constant)));
+        assert!(!kinds(&java).iter().any(|(name, _)| name == "count"));
+    }
+
+    #[test]
+    fn test_chunk_kinds() {
+        let result = parse(
+            "export interface Shape {\n  area(): number;\n}\n\nexport function draw(shape: Shape) {\n  return shape.area();\n}\n",
+            "typescript",
+        );
+        let kinds: Vec<Option<SymbolKind>> = result.chunks.iter().map(|c| c.kind).collect();
+        assert!(kinds.contains(&Some(SymbolKind::Interface)));
+        assert!(kinds.contains(&Some(SymbolKind::Function)));
+        assert_eq!(SymbolKind::Interface.lsp_kind(), 11);
+    }
+
+    #[test]
+    fn test_symbol_outline() {
+        let ts = parse(
+            "namespace Geometry {\n  export class Calculator {\n    add(a: number, b: number) {\n      function check() {}\n      return a + b;\n    }\n  }\n}\n",
+            "typescript",
+        );
+        assert_eq!(ts.symbols.len(), 1);
+        let add = find_symbol(&ts, "Geometry.Calculator.add");
+        assert_eq!(add.id, "method:Geometry.Calculator.add");
+        assert_eq!(add.parent_id.as_deref(), Some("class:Geometry.Calculator"));
+        assert_eq!(add.children[0].qualified_name, "Geometry.Calculator.add.check");
+
+        let rust = parse(
+            "mod parser {\n    pub struct PrismParser;\n    impl PrismParser {\n        pub fn new() -> Self { PrismParser }\n    }\n}\n",
+            "rust",
+        );
+        let new = find_symbol(&rust, "parser::PrismParser::new");
+        assert_eq!(new.parent_id.as_deref(), Some("struct:parser::PrismParser"));
+        assert_eq!(find_symbol(&rust, "parser::PrismParser").children.len(), 1);
+
+        let go = parse("package p\nfunc (c *Calc) Add() {}\ntype Calc struct{}\n", "go");
+        assert_eq!(go.symbols.len(), 1);
+        assert_eq!(go.symbols[0].children[0].qualified_name, "Calc.Add");
+
+        let cpp = parse(
+            "namespace ns {\nclass Calc { int add(int x); };\n}\nint ns::Calc::add(int x) { return x; }\n",
+            "cpp",
+        );
+        let calc = find_symbol(&cpp, "ns::Calc");
+        assert_eq!(calc.children.len(), 2);
+        assert_eq!(calc.children[1].id, "method:ns::Calc::add#2");
+    }
+
+    #[test]
+    fn test_function_values() {
+        let code = r#"
+export const handler = async (req: Request): Promise<Response> => {
+  return respond(req);
+};
+
+export const legacy = function named(a, b = 2) {};
+
+const internal = x => x * 2;
+
+exports.run = async function* () {};
+
+const routes = { "home": () => render() };
+
+class Button {
+  private onClick = async (event: Event) => {};
+}
+"#;
+
+        let result = parse(code, "typescript");
+        let handler = find_function(&result, "handler");
+        assert!(handler.is_async);
+        assert!(handler.is_exported);
+        assert_eq!(handler.start_line, 2);
+        assert!(handler.signature.starts_with("const handler = async (req: Request)"));
+        assert_eq!(handler.return_type.as_deref(), Some("Promise<Response>"));
+
+        let legacy = find_function(&result, "legacy");
+        assert!(!legacy.is_async);
+        assert!(legacy.is_exported);
+        assert_eq!(legacy.parameters.len(), 2);
+
+        let internal = find_function(&result, "internal");
+        assert!(!internal.is_exported);
+        assert_eq!(internal.parameters[0].name, "x");
+
+        let run = find_function(&result, "run");
+        assert!(run.is_async);
+        assert!(run.is_exported);
+
+        assert_eq!(find_function(&result, "home").kind, SymbolKind::Function);
+
+        let on_click = &find_class(&result, "Button").methods[0];
+        assert_eq!(on_click.name, "onClick");
+        assert_eq!(on_click.kind, SymbolKind::Method);
+        assert_eq!(on_click.visibility, Visibility::Private);
+        assert!(on_click.is_async);
+
+        let function_chunks: Vec<&str> = result
+            .chunks
+            .iter()
+            .filter(|c| c.kind == Some(SymbolKind::Function))
+            .flat_map(|c| c.functions.iter().map(|f| f.name.as_str()))
+            .collect();
+        assert!(function_chunks.contains(&"handler"));
+        assert!(function_chunks.contains(&"internal"));
+    }
+
+    #[test]
+    fn test_nested_functions() {
+        let code = r#"
+export function outer() {
+  function inner() {
+    const callback = () => {
+      class Local {
+        run() {}
+      }
+    };
+  }
+}
+"#;
+
+        let result = parse(code, "typescript");
+        assert_eq!(find_function(&result, "outer").depth, 0);
+
+        let inner = find_function(&result, "inner");
+        assert_eq!(inner.depth, 1);
+        assert_eq!(inner.enclosing_symbol.as_deref(), Some("outer"));
+
+        let callback = find_function(&result, "callback");
+        assert_eq!(callback.depth, 2);
+        assert_eq!(callback.enclosing_symbol.as_deref(), Some("outer.inner"));
+
+        let run = find_function(&result, "run");
+        assert_eq!(run.depth, 4);
+        assert_eq!(run.enclosing_symbol.as_deref(), Some("outer.inner.callback.Local"));
+        assert_eq!(find_class(&result, "Local").depth, 3);
+
+        // Out-of-line members name their owner but are not nested
+        let rust = parse("struct S {}\nimpl S {\n    fn new() -> Self { S {} }\n}\n", "rust");
+        let new = find_function(&rust, "new");
+        assert_eq!(new.depth, 0);
+        assert_eq!(new.enclosing_symbol.as_deref(), Some("S"));
+    }
+
+    #[test]
+    fn test_nested_chunking_mode() {
+        let code = "def outer():\n    def inner():\n        return 1\n    return inner()\n\nclass A:\n    def method(self):\n        pass\n";
+
+        let mut parser = PrismParser::new("python").unwrap();
+        let excluded = parser.parse(code).unwrap();
+        let chunked = |result: &ParseResult| -> Vec<String> {
+            result.chunks.iter().flat_map(|c| c.functions.first()).map(|f| f.name.clone()).collect()
+        };
+        assert!(!chunked(&excluded).contains(&"inner".to_string()));
+
+        parser.set_include_nested(true);
+        let included = parser.parse(code).unwrap();
+        assert!(chunked(&included).contains(&"inner".to_string()));
+        assert!(included.chunks.iter().any(|c| c.kind == Some(SymbolKind::Method)));
+        assert!(included.chunks.len() > excluded.chunks.len());
+    }
+
+    #[test]
+    fn test_jsdoc_documentation() {
+        let code = r#"
+/**
+ * Fetch a user by id.
+ *
+ * Falls back to the cache when offline.
+ * @param {string} id - The user id
+ * @param [options] Request options
+ * @returns {Promise<User>} The user record
+ * @throws {NotFoundError} When no user matches
+ */
+export async function getUser(id: string, options?: Options): Promise<User> {
+  return load(id);
+}
+
+// Shared request handler
+export const handler = async (req) => {};
+
+// unrelated
+
+function undocumented() {}
+"#;
+
+        let result = parse(code, "typescript");
+        let doc = find_function(&result, "getUser").documentation.as_ref().unwrap();
+        assert_eq!(doc.start_line, 2);
+        assert_eq!(doc.text, "Fetch a user by id.\n\nFalls back to the cache when offline.");
+        assert_eq!(doc.params.len(), 2);
+        assert_eq!(doc.params[0].name, "id");
+        assert_eq!(doc.params[0].type_annotation.as_deref(), Some("string"));
+        assert_eq!(doc.params[0].description, "The user id");
+        assert_eq!(doc.params[1].name, "options");
+        assert_eq!(doc.returns.as_deref(), Some("The user record"));
+        assert_eq!(doc.throws, vec!["NotFoundError When no user matches"]);
+
+        let handler = find_function(&result, "handler").documentation.as_ref().unwrap();
+        assert_eq!(handler.text, "Shared request handler");
+        assert!(find_function(&result, "undocumented").documentation.is_none());
+
+        let chunk = result.chunks.iter().find(|c| c.text.contains("getUser")).unwrap();
+        assert_eq!(chunk.start_line, 2);
+        assert_eq!(chunk.documentation.as_ref().unwrap().params.len(), 2);
+    }
+
+    #[test]
+    fn test_docstrings_and_line_docs() {
+        let python = parse(
+            r#"# Area helpers
+class Shape:
+    """A shape.
+
+    Subclasses override area.
+    """
+
+    def area(self, scale):
+        '''Compute the area.
+
+        :param float scale: Scale factor
+        :returns: The area
+        :raises ValueError: If scale is negative
+        '''
+"#,
+            "python",
+        );
+        let shape = find_class(&python, "Shape").documentation.as_ref().unwrap();
+        assert_eq!(shape.text, "A shape.\n\nSubclasses override area.");
+        let area = find_function(&python, "area").documentation.as_ref().unwrap();
+        assert_eq!(area.text, "Compute the area.");
+        assert_eq!(area.params[0].name, "scale");
+        assert_eq!(area.params[0].type_annotation.as_deref(), Some("float"));
+        assert_eq!(area.returns.as_deref(), Some("The area"));
+        assert_eq!(area.throws, vec!["ValueError If scale is negative"]);
+
+        let rust = parse(
+            "//! Module docs\n\n/// A point.\n///\n/// Used everywhere.\n#[derive(Debug)]\npub struct Point {\n    x: i32,\n}\n",
+            "rust",
+        );
+        let point = find_class(&rust, "Point").documentation.as_ref().unwrap();
+        assert_eq!(point.text, "A point.\n\nUsed everywhere.");
+        assert_eq!(point.start_line, 3);
+
+        let java = parse(
+            "class Calc {\n    /**\n     * Adds numbers.\n     * @param a first\n     * @return the sum\n     */\n    @Override\n    public int add(int a) { return a; }\n}\n",
+            "java",
+        );
+        let add = find_function(&java, "add").documentation.as_ref().unwrap();
+        assert_eq!(add.text, "Adds numbers.");
+        assert_eq!(add.params[0].description, "first");
+        assert_eq!(add.returns.as_deref(), Some("the sum"));
+
+        let go = parse("package p\n\n// Cache stores values.\ntype Cache struct{}\n", "go");
+        assert_eq!(find_class(&go, "Cache").documentation.as_ref().unwrap().text, "Cache stores values.");
+    }
+}
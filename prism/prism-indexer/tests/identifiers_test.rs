@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{CodeChunk, IdentifierCount, IdentifierKind, PrismParser};
+
+    fn chunks(code: &str, language: &str) -> Vec<CodeChunk> {
+        let mut parser = PrismParser::new(language).unwrap();
+        parser.parse(code).unwrap().chunks
+    }
+
+    fn summary(counts: &[IdentifierCount]) -> Vec<(&str, IdentifierKind, usize)> {
+        counts.iter().map(|c| (c.name.as_str(), c.kind, c.count)).collect()
+    }
+
+    #[test]
+    fn test_typescript_identifiers() {
+        let code = r#"
+export class UserService {
+    private cache: Map<string, User>;
+
+    async load(id: string): Promise<User> {
+        const user = await fetchUser(id);
+        this.cache.set(id, user);
+        return user;
+    }
+}
+"#;
+
+        let chunk = chunks(code, "typescript").remove(0);
+        assert_eq!(
+            summary(&chunk.defines),
+            vec![
+                ("UserService", IdentifierKind::Type, 1),
+                ("cache", IdentifierKind::Property, 1),
+                ("load", IdentifierKind::Property, 1),
+                ("id", IdentifierKind::Value, 1),
+                ("user", IdentifierKind::Value, 1),
+            ]
+        );
+        // Uses of names declared in the chunk are not references
+        assert_eq!(
+            summary(&chunk.references),
+            vec![
+                ("Map", IdentifierKind::Type, 1),
+                ("User", IdentifierKind::Type, 2),
+                ("Promise", IdentifierKind::Type, 1),
+                ("fetchUser", IdentifierKind::Value, 1),
+                ("set", IdentifierKind::Property, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_python_and_rust_identifiers() {
+        let code = "def total(items: list[Item], rate):\n    result = sum(i.price for i in items)\n    return result * rate\n";
+        let chunk = chunks(code, "python").remove(0);
+        let defines: Vec<&str> = chunk.defines.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(defines, vec!["total", "items", "rate", "result", "i"]);
+        assert!(summary(&chunk.references).contains(&("Item", IdentifierKind::Type, 1)));
+        assert!(summary(&chunk.references).contains(&("price", IdentifierKind::Property, 1)));
+
+        let code = "pub fn area(shape: &Shape) -> f64 {\n    let (w, h) = shape.size();\n    w * h * SCALE\n}\n";
+        let chunk = chunks(code, "rust").remove(0);
+        assert_eq!(
+            summary(&chunk.defines),
+            vec![
+                ("area", IdentifierKind::Value, 1),
+                ("shape", IdentifierKind::Value, 1),
+                ("w", IdentifierKind::Value, 1),
+                ("h", IdentifierKind::Value, 1),
+            ]
+        );
+        assert_eq!(
+            summary(&chunk.references),
+            vec![
+                ("Shape", IdentifierKind::Type, 1),
+                ("size", IdentifierKind::Property, 1),
+                ("SCALE", IdentifierKind::Value, 1),
+            ]
+        );
+    }
+}
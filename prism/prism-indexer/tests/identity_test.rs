@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{content_hash, split_large_chunk, CodeChunk, PrismParser};
+    use std::collections::HashSet;
+
+    fn chunks(code: &str, language: &str, path: &str) -> Vec<CodeChunk> {
+        let mut parser = PrismParser::new(language).unwrap();
+        parser.set_file_path(path);
+        parser.parse(code).unwrap().chunks
+    }
+
+    fn find<'a>(chunks: &'a [CodeChunk], name: &str) -> &'a CodeChunk {
+        chunks
+            .iter()
+            .find(|c| c.qualified_name.as_deref() == Some(name))
+            .unwrap()
+    }
+
+    const SOURCE: &str = r#"export class Cart {
+  total(): number {
+    return 0;
+  }
+}
+
+export function checkout(cart: Cart) {
+  return cart.total();
+}
+
+const config = {
+  retries: 3,
+  timeout: 1000,
+  endpoint: 'https://example.com/api',
+};
+"#;
+
+    #[test]
+    fn test_ids_are_deterministic() {
+        let first = chunks(SOURCE, "typescript", "src/cart.ts");
+        let second = chunks(SOURCE, "typescript", "src/cart.ts");
+        let ids = |chunks: &[CodeChunk]| chunks.iter().map(|c| c.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&first), ids(&second));
+        assert_eq!(first.iter().map(|c| &c.id).collect::<HashSet<_>>().len(), first.len());
+
+        assert_eq!(find(&first, "checkout").kind.map(|k| k.as_str()), Some("function"));
+
+        // The same code in another file is another chunk
+        let moved = chunks(SOURCE, "typescript", "src/shop/cart.ts");
+        assert_ne!(find(&first, "Cart").id, find(&moved, "Cart").id);
+        assert_ne!(find(&first, "Cart").stable_id, find(&moved, "Cart").stable_id);
+        assert_eq!(find(&first, "Cart").content_hash, find(&moved, "Cart").content_hash);
+    }
+
+    #[test]
+    fn test_identity_survives_moves_and_edits() {
+        let original = chunks(SOURCE, "typescript", "src/cart.ts");
+
+        // Functions reordered and reformatted: nothing changed
+        let reordered = r#"const config = {
+  retries: 3,   
+  timeout: 1000,
+  endpoint: 'https://example.com/api',
+};
+
+
+export function checkout(cart: Cart) {
+
+  return cart.total();
+}
+
+export class Cart {
+  total(): number {
+    return 0;
+  }
+}
+"#;
+        let moved = chunks(reordered, "typescript", "src/cart.ts");
+        for name in ["Cart", "checkout"] {
+            assert_eq!(find(&original, name).id, find(&moved, name).id);
+        }
+        let top_level = |chunks: &[CodeChunk]| chunks.iter().find(|c| c.qualified_name.is_none()).unwrap().id.clone();
+        assert_eq!(top_level(&original), top_level(&moved));
+
+        // An edited body keeps its identity but not its content hash
+        let edited = SOURCE.replace("return cart.total();", "return cart.total() * 2;");
+        let edited = chunks(&edited, "typescript", "src/cart.ts");
+        let (before, after) = (find(&original, "checkout"), find(&edited, "checkout"));
+        assert_eq!(before.stable_id, after.stable_id);
+        assert_ne!(before.content_hash, after.content_hash);
+        assert_ne!(before.id, after.id);
+        assert_eq!(find(&original, "Cart").id, find(&edited, "Cart").id);
+
+        assert_eq!(content_hash("  a\r\n\n    b  \n"), content_hash("a\n  b"));
+        assert_ne!(content_hash("a\n  b"), content_hash("a\nb"));
+    }
+
+    #[test]
+    fn test_overloads_and_split_parts() {
+        let code = r#"class Shape {
+    double area(int side) { return side * side; }
+
+    double area(int width, int height) { return width * height; }
+}
+"#;
+        let mut parser = PrismParser::new("java").unwrap();
+        parser.set_include_nested(true);
+        parser.set_file_path("Shape.java");
+        let nested = parser.parse(code).unwrap().chunks;
+        let areas: HashSet<&str> = nested
+            .iter()
+            .filter(|c| c.qualified_name.as_deref() == Some("Shape.area"))
+            .map(|c| c.stable_id.as_str())
+            .collect();
+        assert_eq!(areas.len(), 2);
+
+        let body: String = (0..40).map(|i| format!("  step{}();\n", i)).collect();
+        let code = format!("function long() {{\n{}}}\n", body);
+        let chunk = chunks(&code, "javascript", "long.js").remove(0);
+        let parts = split_large_chunk(&chunk, 60);
+        let again = split_large_chunk(&chunk, 60);
+        assert!(parts.len() > 1);
+        assert_eq!(
+            parts.iter().map(|p| &p.id).collect::<Vec<_>>(),
+            again.iter().map(|p| &p.id).collect::<Vec<_>>()
+        );
+        assert_eq!(parts.iter().map(|p| &p.stable_id).collect::<HashSet<_>>().len(), parts.len());
+        assert!(parts.iter().all(|p| p.content_hash == content_hash(&p.text)));
+    }
+}
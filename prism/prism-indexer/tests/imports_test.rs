@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{BindingKind, ImportInfo, ImportKind, PrismParser};
+
+    fn imports(code: &str, language: &str) -> Vec<ImportInfo> {
+        let mut parser = PrismParser::new(language).unwrap();
+        parser.parse(code).unwrap().imports
+    }
+
+    fn bindings(import: &ImportInfo) -> Vec<(&str, &str, BindingKind)> {
+        import
+            .bindings
+            .iter()
+            .map(|b| (b.imported.as_str(), b.local.as_str(), b.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_es_module_imports() {
+        let code = r#"
+import React, * as all from 'react';
+import { useState as useLocal, useEffect } from "react";
+import './polyfills';
+import fs = require('fs');
+export * from './barrel';
+export { default as Button } from './button';
+"#;
+
+        let result = imports(code, "typescript");
+        assert_eq!(result.len(), 6);
+        assert_eq!(
+            bindings(&result[0]),
+            vec![("default", "React", BindingKind::Default), ("*", "all", BindingKind::Namespace)]
+        );
+        assert_eq!(
+            bindings(&result[1]),
+            vec![("useState", "useLocal", BindingKind::Named), ("useEffect", "useEffect", BindingKind::Named)]
+        );
+        assert_eq!(result[1].imported_names, vec!["useLocal", "useEffect"]);
+        assert_eq!(result[2].source, "./polyfills");
+        assert!(result[2].bindings.is_empty());
+        assert_eq!(result[3].kind, ImportKind::Require);
+        assert_eq!(bindings(&result[4]), vec![("*", "*", BindingKind::Wildcard)]);
+        assert_eq!(bindings(&result[5]), vec![("default", "Button", BindingKind::Default)]);
+    }
+
+    #[test]
+    fn test_commonjs_and_dynamic_imports() {
+        let code = r#"
+const path = require('path');
+const { readFile, writeFile: save } = require('fs/promises');
+const join = require('path').join;
+async function load() {
+  const mod = await import('./plugin');
+  register(require('./side'));
+}
+"#;
+
+        let result = imports(code, "javascript");
+        assert_eq!(result.len(), 5);
+        assert_eq!(bindings(&result[0]), vec![("*", "path", BindingKind::Namespace)]);
+        assert_eq!(
+            bindings(&result[1]),
+            vec![("readFile", "readFile", BindingKind::Named), ("writeFile", "save", BindingKind::Named)]
+        );
+        assert_eq!(bindings(&result[2]), vec![("join", "join", BindingKind::Named)]);
+        assert_eq!(result[3].kind, ImportKind::Dynamic);
+        assert_eq!(bindings(&result[3]), vec![("*", "mod", BindingKind::Namespace)]);
+        assert_eq!(result[4].source, "./side");
+        assert!(result[4].bindings.is_empty());
+    }
+
+    #[test]
+    fn test_python_imports() {
+        let code = "import os.path, numpy as np\nfrom ..models.user import User as U, Admin\nfrom . import sibling\nfrom x import *\n";
+
+        let result = imports(code, "python");
+        assert_eq!(result.len(), 5);
+        assert_eq!(bindings(&result[0]), vec![("*", "os", BindingKind::Namespace)]);
+        assert_eq!(result[1].source, "numpy");
+        assert_eq!(bindings(&result[1]), vec![("*", "np", BindingKind::Namespace)]);
+        assert_eq!(result[2].source, "..models.user");
+        assert_eq!(result[2].relative_level, 2);
+        assert_eq!(
+            bindings(&result[2]),
+            vec![("User", "U", BindingKind::Named), ("Admin", "Admin", BindingKind::Named)]
+        );
+        assert_eq!(result[3].relative_level, 1);
+        assert_eq!(bindings(&result[4]), vec![("*", "*", BindingKind::Wildcard)]);
+    }
+
+    #[test]
+    fn test_go_and_java_imports() {
+        let go = imports(
+            "package p\nimport (\n  f \"fmt\"\n  _ \"lib/pq\"\n  . \"math\"\n  \"net/http\"\n)\n",
+            "go",
+        );
+        assert_eq!(go.len(), 4);
+        assert_eq!(bindings(&go[0]), vec![("*", "f", BindingKind::Namespace)]);
+        assert!(go[1].bindings.is_empty());
+        assert_eq!(bindings(&go[2]), vec![("*", "*", BindingKind::Wildcard)]);
+        assert_eq!(go[3].source, "net/http");
+        assert_eq!(bindings(&go[3]), vec![("*", "http", BindingKind::Namespace)]);
+
+        let java = imports(
+            "import java.util.List;\nimport static org.junit.Assert.assertEquals;\nimport java.io.*;\n",
+            "java",
+        );
+        assert_eq!(java[0].source, "java.util");
+        assert_eq!(bindings(&java[0]), vec![("List", "List", BindingKind::Named)]);
+        assert_eq!(java[1].source, "org.junit.Assert");
+        assert_eq!(java[2].source, "java.io");
+        assert_eq!(bindings(&java[2]), vec![("*", "*", BindingKind::Wildcard)]);
+    }
+
+    #[test]
+    fn test_rust_use_trees() {
+        let code = "use std::{io::{self, Read}, fmt::Result as FmtResult};\nuse crate::parser::*;\nuse serde;\n";
+
+        let result = imports(code, "rust");
+        let sources: Vec<&str> = result.iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(sources, vec!["std", "std::io", "std::fmt", "crate::parser", "serde"]);
+        assert_eq!(bindings(&result[0]), vec![("io", "io", BindingKind::Named)]);
+        assert_eq!(bindings(&result[1]), vec![("Read", "Read", BindingKind::Named)]);
+        assert_eq!(bindings(&result[2]), vec![("Result", "FmtResult", BindingKind::Named)]);
+        assert_eq!(bindings(&result[3]), vec![("*", "*", BindingKind::Wildcard)]);
+        assert_eq!(bindings(&result[4]), vec![("*", "serde", BindingKind::Namespace)]);
+    }
+
+    #[test]
+    fn test_type_only_imports() {
+        let code = r#"
+import type { Props } from './props';
+import { type State, useStore } from './store';
+import type Config from './config';
+export type { Theme } from './theme';
+import { render } from './render';
+"#;
+
+        let result = imports(code, "typescript");
+        let type_only: Vec<bool> = result.iter().map(|i| i.is_type_only).collect();
+        assert_eq!(type_only, vec![true, false, true, true, false]);
+        assert!(result[0].bindings[0].is_type_only);
+
+        let store: Vec<bool> = result[1].bindings.iter().map(|b| b.is_type_only).collect();
+        assert_eq!(store, vec![true, false]);
+        assert!(!result[4].bindings[0].is_type_only);
+
+        let python = imports(
+            "from typing import TYPE_CHECKING\nif TYPE_CHECKING:\n    from .models import User\nelse:\n    import json\n",
+            "python",
+        );
+        assert!(!python[0].is_type_only);
+        assert!(python[1].is_type_only);
+        assert!(python[1].bindings[0].is_type_only);
+        assert!(!python[2].is_type_only);
+    }
+}
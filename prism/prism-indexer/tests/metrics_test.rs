@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{ComplexityMetrics, PrismParser};
+
+    fn metrics(code: &str, language: &str) -> Vec<(String, ComplexityMetrics)> {
+        let mut parser = PrismParser::new(language).unwrap();
+        let result = parser.parse(code).unwrap();
+        result.functions.into_iter().map(|f| (f.name, f.metrics)).collect()
+    }
+
+    #[test]
+    fn test_typescript_complexity() {
+        let code = r#"
+function sumOfPrimes(max: number): number {
+  let total = 0;
+  outer: for (let i = 1; i <= max; ++i) {
+    for (let j = 2; j < i; ++j) {
+      if (i % j === 0) {
+        continue outer;
+      }
+    }
+    total += i;
+  }
+  return total;
+}
+
+function getWords(n: number) {
+  switch (n) {
+    case 1:
+      return "one";
+    case 2:
+      return "a couple";
+    default:
+      return "lots";
+  }
+}
+
+const pick = (a, b, c) => a && b && c || (a ? b : c);
+
+function branches(x) {
+  // comment line
+
+  if (x > 1) {
+    return 1;
+  } else if (x < 0) {
+    return 2;
+  } else {
+    items.forEach(i => { if (i) { log(i); } });
+  }
+}
+"#;
+
+        let result = metrics(code, "typescript");
+        let summary: Vec<(&str, usize, usize, usize)> = result
+            .iter()
+            .map(|(name, m)| (name.as_str(), m.cyclomatic, m.cognitive, m.max_nesting))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("sumOfPrimes", 4, 7, 3),
+                ("getWords", 3, 1, 1),
+                ("pick", 5, 3, 1),
+                ("branches", 4, 6, 3),
+            ]
+        );
+
+        let branches = &result[3].1;
+        assert_eq!(branches.parameters, 1);
+        // The comment and the blank line are not code
+        assert_eq!(branches.lines_of_code, 9);
+        assert_eq!(result[1].1.statements, 4);
+    }
+
+    #[test]
+    fn test_python_and_rust_complexity() {
+        let code = r#"
+class Parser:
+    def f(self, a, b=1):
+        try:
+            if a and b:
+                return [x for x in a if x]
+            elif b:
+                pass
+        except ValueError:
+            raise
+"#;
+        let result = metrics(code, "python");
+        assert_eq!(
+            result[0].1,
+            ComplexityMetrics {
+                cyclomatic: 5,
+                cognitive: 4,
+                max_nesting: 1,
+                statements: 5,
+                parameters: 2,
+                lines_of_code: 8,
+            }
+        );
+
+        let code = r#"
+pub fn classify(value: Option<u8>, strict: bool) -> &'static str {
+    let Some(v) = value else { return "none" };
+    'scan: loop {
+        match v {
+            0 => break 'scan,
+            1 | 2 if strict => return "small",
+            _ => {}
+        }
+    }
+    "other"
+}
+"#;
+        let result = metrics(code, "rust");
+        let classify = &result[0].1;
+        assert_eq!((classify.cyclomatic, classify.cognitive, classify.max_nesting), (4, 4, 2));
+        assert_eq!(classify.parameters, 2);
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{
+        split_large_chunk_with_overlap, ChunkingOptions, CodeChunk, HeuristicTokenizer, PrismParser, Tokenizer,
+    };
+
+    fn long_function(statements: usize) -> String {
+        let body: String = (0..statements)
+            .map(|i| format!("  const total{} = items[{}].price * items[{}].quantity;\n", i, i, i))
+            .collect();
+        format!("function summarize(items) {{\n{}  return items;\n}}\n", body)
+    }
+
+    fn chunks(code: &str, options: &ChunkingOptions) -> Vec<CodeChunk> {
+        let mut parser = PrismParser::new("javascript").unwrap();
+        parser.set_tokenizer("heuristic").unwrap();
+        parser.set_chunking_options(options).unwrap();
+        parser.parse(code).unwrap().chunks
+    }
+
+    /// Lines of a piece that are its own, after the overlap
+    fn own_text(chunk: &CodeChunk) -> String {
+        let repeated = chunk.overlap.map_or(0, |range| range.end_line - range.start_line + 1);
+        chunk.text.lines().skip(repeated).collect::<Vec<_>>().join("\n")
+    }
+
+    #[test]
+    fn test_split_pieces_share_overlap() {
+        let code = long_function(40);
+        let whole = chunks(&code, &ChunkingOptions::default()).remove(0);
+
+        let tokenizer = HeuristicTokenizer;
+        let pieces = split_large_chunk_with_overlap(&whole, 150, 40, &tokenizer);
+        assert!(pieces.len() > 2);
+        assert!(pieces[0].overlap.is_none());
+
+        // An overlap as large as the target still leaves room for new lines
+        let greedy = split_large_chunk_with_overlap(&whole, 150, 149, &tokenizer);
+        for piece in &greedy {
+            assert!(piece.text.lines().map(|line| tokenizer.count_tokens(line)).sum::<usize>() <= 150);
+        }
+
+        for pair in pieces.windows(2) {
+            let overlap = pair[1].overlap.unwrap();
+            assert_eq!(overlap.start_line, pair[1].start_line);
+            assert_eq!(overlap.end_line, pair[0].end_line);
+
+            // The repeated lines are the tail of the previous piece
+            let repeated: Vec<&str> = pair[1].text.lines().take(overlap.end_line - overlap.start_line + 1).collect();
+            assert!(pair[0].text.ends_with(&repeated.join("\n")));
+            assert!(repeated.iter().map(|line| tokenizer.count_tokens(line)).sum::<usize>() <= 40);
+        }
+
+        // Dropping the overlaps reassembles the original chunk
+        let own: Vec<String> = pieces.iter().map(own_text).collect();
+        assert_eq!(own.join("\n"), whole.text);
+    }
+
+    #[test]
+    fn test_chunking_options_control_overlap() {
+        let code = long_function(60);
+        let split = |overlap_tokens| {
+            let options = ChunkingOptions {
+                target_tokens: Some(200),
+                max_tokens: Some(200),
+                overlap_tokens,
+                ..Default::default()
+            };
+            chunks(&code, &options)
+        };
+
+        // Without an explicit overlap, pieces share up to a quarter of the target
+        let pieces = split(None);
+        assert!(pieces.len() > 1);
+        assert!(pieces[1..].iter().all(|c| c.overlap.is_some()));
+
+        let disjoint = split(Some(0));
+        assert!(disjoint.iter().all(|c| c.overlap.is_none()));
+        assert!(disjoint.windows(2).all(|pair| pair[0].end_line + 1 == pair[1].start_line));
+    }
+
+    #[test]
+    fn test_long_top_level_code_overlaps() {
+        let code: String = (0..260).map(|i| format!("config.set('key{}', {});\n", i, i)).collect();
+        let options = ChunkingOptions {
+            max_lines: Some(100),
+            overlap_tokens: Some(30),
+            ..Default::default()
+        };
+        let pieces = chunks(&code, &options);
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[0].start_line, 1);
+
+        for pair in pieces.windows(2) {
+            let overlap = pair[1].overlap.unwrap();
+            assert_eq!(overlap.end_line, pair[0].end_line);
+            assert!(pair[1].text.starts_with(&format!("config.set('key{}'", overlap.start_line - 1)));
+        }
+
+        // Editing the repeated tail of a piece leaves the next piece's ids alone
+        let last_line = pieces[0].end_line;
+        let edited = code.replace(&format!("'key{}', {}", last_line - 1, last_line - 1), "'edited', 0");
+        let edited = chunks(&edited, &options);
+        assert_ne!(edited[0].id, pieces[0].id);
+        assert_ne!(edited[1].text, pieces[1].text);
+        assert_eq!(edited[1].content_hash, pieces[1].content_hash);
+        assert_eq!(edited[1].id, pieces[1].id);
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{resolve_imports, ImportGraph, ParseResult, PrismParser, ResolveOptions};
+
+    fn parse(code: &str, language: &str) -> ParseResult {
+        let mut parser = PrismParser::new(language).unwrap();
+        parser.parse(code).unwrap()
+    }
+
+    fn resolve(files: &[(&str, &str, &str)], options: &ResolveOptions) -> ImportGraph {
+        let results: Vec<(&str, ParseResult)> = files
+            .iter()
+            .map(|(path, language, code)| (*path, parse(code, language)))
+            .collect();
+        let inputs: Vec<(&str, &ParseResult)> = results.iter().map(|(path, result)| (*path, result)).collect();
+        resolve_imports(&inputs, options)
+    }
+
+    fn edges(graph: &ImportGraph) -> Vec<(&str, &str)> {
+        graph.edges.iter().map(|e| (e.from.as_str(), e.to.as_str())).collect()
+    }
+
+    #[test]
+    fn test_typescript_resolution() {
+        let graph = resolve(
+            &[
+                (
+                    "src/app.ts",
+                    "typescript",
+                    "import { a } from './utils';\nimport type { B } from './models/index.js';\nimport c from '../lib/c';\nimport x from './missing';\nimport React from 'react';\n",
+                ),
+                ("src/utils/index.ts", "typescript", "export const a = 1;\n"),
+                ("src/models/index.ts", "typescript", "export type B = string;\n"),
+                ("lib/c.js", "javascript", "module.exports = 1;\n"),
+            ],
+            &ResolveOptions::default(),
+        );
+
+        assert_eq!(
+            edges(&graph),
+            vec![
+                ("src/app.ts", "src/utils/index.ts"),
+                ("src/app.ts", "src/models/index.ts"),
+                ("src/app.ts", "lib/c.js"),
+            ]
+        );
+        assert!(graph.edges[1].is_type_only);
+        assert_eq!(graph.unresolved.len(), 1);
+        assert_eq!(graph.unresolved[0].source, "./missing");
+        assert_eq!(graph.external.len(), 1);
+        assert_eq!(graph.external[0].source, "react");
+    }
+
+    #[test]
+    fn test_python_and_rust_resolution() {
+        let graph = resolve(
+            &[
+                ("pkg/api/views.py", "python", "from ..models import User\nfrom . import forms\nimport pkg.util\nimport os\n"),
+                ("pkg/models/__init__.py", "python", "class User: pass\n"),
+                ("pkg/api/forms.py", "python", "X = 1\n"),
+                ("pkg/util.py", "python", "Y = 2\n"),
+            ],
+            &ResolveOptions::default(),
+        );
+        assert_eq!(
+            edges(&graph),
+            vec![
+                ("pkg/api/views.py", "pkg/models/__init__.py"),
+                ("pkg/api/views.py", "pkg/api/forms.py"),
+                ("pkg/api/views.py", "pkg/util.py"),
+            ]
+        );
+        assert_eq!(graph.external.len(), 1);
+
+        let graph = resolve(
+            &[
+                ("src/lib.rs", "rust", "mod parser;\nmod config { pub struct Config; }\nuse crate::parser::Parser;\nuse crate::config::Config;\nuse crate::missing;\nuse std::io;\n"),
+                ("src/parser/mod.rs", "rust", "use super::types::*;\nuse self::lexer::Token;\nuse crate::gone::X;\nuse crate::config;\n"),
+                ("src/parser/lexer.rs", "rust", "pub struct Token;\n"),
+                ("src/types.rs", "rust", "pub struct Node;\n"),
+            ],
+            &ResolveOptions::default(),
+        );
+        assert_eq!(
+            edges(&graph),
+            vec![
+                ("src/lib.rs", "src/parser/mod.rs"),
+                // Inline modules of the crate root resolve to the root
+                ("src/lib.rs", "src/lib.rs"),
+                ("src/parser/mod.rs", "src/types.rs"),
+                ("src/parser/mod.rs", "src/parser/lexer.rs"),
+                ("src/parser/mod.rs", "src/lib.rs"),
+            ]
+        );
+        assert_eq!(graph.external[0].source, "std");
+        let unresolved: Vec<(&str, &str)> = graph
+            .unresolved
+            .iter()
+            .map(|u| (u.from.as_str(), u.source.as_str()))
+            .collect();
+        assert_eq!(unresolved, vec![("src/lib.rs", "crate"), ("src/parser/mod.rs", "crate::gone")]);
+    }
+
+    #[test]
+    fn test_go_and_java_resolution() {
+        let options = ResolveOptions {
+            go_module: Some("github.com/acme/app".to_string()),
+        };
+        let graph = resolve(
+            &[
+                ("cmd/main.go", "go", "package main\n\nimport (\n\t\"fmt\"\n\t\"github.com/acme/app/pkg/util\"\n\t\"github.com/acme/app/pkg/none\"\n)\n"),
+                ("pkg/util/strings.go", "go", "package util\n"),
+                ("pkg/util/strings_test.go", "go", "package util\n"),
+                ("pkg/util/numbers.go", "go", "package util\n"),
+            ],
+            &options,
+        );
+        assert_eq!(
+            edges(&graph),
+            vec![("cmd/main.go", "pkg/util/numbers.go"), ("cmd/main.go", "pkg/util/strings.go")]
+        );
+        assert_eq!(graph.unresolved[0].source, "github.com/acme/app/pkg/none");
+        assert_eq!(graph.external[0].source, "fmt");
+
+        let graph = resolve(
+            &[
+                (
+                    "src/main/java/com/acme/App.java",
+                    "java",
+                    "import com.acme.util.Helper;\nimport com.acme.model.*;\nimport java.util.List;\nclass App {}\n",
+                ),
+                ("src/main/java/com/acme/util/Helper.java", "java", "class Helper {}\n"),
+                ("src/main/java/com/acme/model/User.java", "java", "class User {}\n"),
+            ],
+            &ResolveOptions::default(),
+        );
+        assert_eq!(
+            edges(&graph),
+            vec![
+                ("src/main/java/com/acme/App.java", "src/main/java/com/acme/util/Helper.java"),
+                ("src/main/java/com/acme/App.java", "src/main/java/com/acme/model/User.java"),
+            ]
+        );
+        assert_eq!(graph.external[0].source, "java.util");
+    }
+}
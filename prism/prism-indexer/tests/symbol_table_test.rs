@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{ParseResult, PrismParser, ResolveOptions, SymbolTable};
+
+    fn build(files: &[(&str, &str, &str)]) -> SymbolTable {
+        let results: Vec<(&str, ParseResult)> = files
+            .iter()
+            .map(|(path, language, code)| {
+                let mut parser = PrismParser::new(language).unwrap();
+                (*path, parser.parse(code).unwrap())
+            })
+            .collect();
+        let inputs: Vec<(&str, &ParseResult)> = results.iter().map(|(path, result)| (*path, result)).collect();
+        SymbolTable::build(&inputs, &ResolveOptions::default())
+    }
+
+    fn locations(definitions: &[&prism_indexer::Definition]) -> Vec<(String, String)> {
+        definitions
+            .iter()
+            .map(|d| (d.file.clone(), d.qualified_name.clone()))
+            .collect()
+    }
+
+    fn project() -> SymbolTable {
+        build(&[
+            (
+                "src/app.ts",
+                "typescript",
+                r#"import { format as fmt } from './utils';
+import * as models from './models';
+import Store from './store';
+
+export function main() {
+    const store = new Store();
+    return fmt(models.createUser('a'));
+}
+
+function format() {
+    return 'local';
+}
+"#,
+            ),
+            (
+                "src/utils/index.ts",
+                "typescript",
+                "export * from './format';\n",
+            ),
+            (
+                "src/utils/format.ts",
+                "typescript",
+                "export function format(value: string) {\n    return value.trim();\n}\n",
+            ),
+            (
+                "src/models.ts",
+                "typescript",
+                "export function createUser(name: string) {\n    return { name };\n}\n",
+            ),
+            (
+                "src/store.ts",
+                "typescript",
+                "export default class Store {\n    save() {\n        return true;\n    }\n}\n",
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_find_definition_through_imports() {
+        let table = project();
+
+        assert_eq!(table.lookup("Store.save").len(), 1);
+        assert_eq!(table.definitions_in("src/store.ts").len(), 2);
+
+        // Aliased import followed through `export *`
+        assert_eq!(
+            locations(&table.find_definition("fmt", "src/app.ts")),
+            vec![("src/utils/format.ts".to_string(), "format".to_string())]
+        );
+        // Local declarations shadow the project
+        assert_eq!(
+            locations(&table.find_definition("format", "src/app.ts")),
+            vec![("src/app.ts".to_string(), "format".to_string())]
+        );
+        // Namespace member and default import
+        assert_eq!(
+            locations(&table.find_definition("models.createUser", "src/app.ts")),
+            vec![("src/models.ts".to_string(), "createUser".to_string())]
+        );
+        assert_eq!(
+            locations(&table.find_definition("Store", "src/app.ts")),
+            vec![("src/store.ts".to_string(), "Store".to_string())]
+        );
+        // Not imported: falls back to exported symbols of the project
+        assert_eq!(
+            locations(&table.find_definition("createUser", "src/utils/format.ts")),
+            vec![("src/models.ts".to_string(), "createUser".to_string())]
+        );
+        assert!(table.find_definition("missing", "src/app.ts").is_empty());
+
+        // Barrels that all re-export each other end the search
+        let paths: Vec<String> = (0..6).map(|i| format!("src/barrel{}.ts", i)).collect();
+        let codes: Vec<String> = (0..6)
+            .map(|i| {
+                (0..6)
+                    .filter(|&j| j != i)
+                    .map(|j| format!("export * from './barrel{}';\n", j))
+                    .collect()
+            })
+            .collect();
+        let mut files: Vec<(&str, &str, &str)> = paths
+            .iter()
+            .zip(&codes)
+            .map(|(path, code)| (path.as_str(), "typescript", code.as_str()))
+            .collect();
+        files.push(("src/main.ts", "typescript", "import { nowhere } from './barrel0';\n"));
+        assert!(build(&files).find_definition("nowhere", "src/main.ts").is_empty());
+    }
+
+    #[test]
+    fn test_find_references() {
+        let table = project();
+
+        // Used through the `fmt` alias; `format` in app.ts is the local one
+        let format = table.lookup("format").iter().find(|d| d.file == "src/utils/format.ts").unwrap();
+        let references = table.find_references(format);
+        assert_eq!(references.len(), 1);
+        assert_eq!((references[0].file.as_str(), references[0].count), ("src/app.ts", 1));
+
+        let create_user = &table.lookup("createUser")[0];
+        let references = table.find_references(create_user);
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].file, "src/app.ts");
+        assert_eq!(references[0].start_line, 5);
+
+        let local_format = table.lookup("format").iter().find(|d| d.file == "src/app.ts").unwrap();
+        assert!(table.find_references(local_format).is_empty());
+
+        let store = &table.lookup("Store")[0];
+        let references = table.find_references(store);
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].count, 1);
+
+        let save = &table.lookup("Store.save")[0];
+        assert!(table.find_references(save).is_empty());
+    }
+
+    #[test]
+    fn test_rust_and_go_scopes() {
+        let table = build(&[
+            ("src/lib.rs", "rust", "mod parser;\nuse crate::parser::Parser;\n\npub fn run() {\n    Parser::new();\n}\n"),
+            ("src/parser.rs", "rust", "pub struct Parser {}\n\nimpl Parser {\n    pub fn new() -> Self {\n        Parser {}\n    }\n}\n"),
+        ]);
+        assert_eq!(
+            locations(&table.find_definition("Parser::new", "src/lib.rs")),
+            vec![("src/parser.rs".to_string(), "Parser::new".to_string())]
+        );
+
+        let table = build(&[
+            ("pkg/a.go", "go", "package pkg\n\nfunc Run() {\n\thelper()\n}\n"),
+            ("pkg/b.go", "go", "package pkg\n\nfunc helper() {}\n"),
+            ("other/c.go", "go", "package other\n\nfunc helper() {}\n"),
+        ]);
+        assert_eq!(
+            locations(&table.find_definition("helper", "pkg/a.go")),
+            vec![("pkg/b.go".to_string(), "helper".to_string())]
+        );
+    }
+}
@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tests {
+    use prism_indexer::{ParseResult, PrismParser, ResolveOptions, SymbolTable};
+
+    fn parse(code: &str, language: &str) -> ParseResult {
+        let mut parser = PrismParser::new(language).unwrap();
+        parser.parse(code).unwrap()
+    }
+
+    fn tests(result: &ParseResult) -> Vec<(&str, Vec<&str>)> {
+        result
+            .functions
+            .iter()
+            .filter(|f| f.is_test)
+            .map(|f| (f.name.as_str(), f.test_subjects.iter().map(String::as_str).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_and_python_tests() {
+        let code = r#"
+pub fn parse_config(input: &str) -> Config {
+    Config::new(input)
+}
+
+fn helper() -> u32 {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> &'static str {
+        "a = 1"
+    }
+
+    #[test]
+    fn test_parse_config_reads_keys() {
+        let config = parse_config(fixture());
+        assert_eq!(config.len(), helper() as usize);
+    }
+}
+"#;
+        let result = parse(code, "rust");
+        assert_eq!(
+            tests(&result),
+            vec![("fixture", vec![]), ("test_parse_config_reads_keys", vec!["parse_config", "helper"])]
+        );
+
+        let code = r#"
+import pytest
+from app.calculator import Calculator, add
+from app import utils
+
+
+@pytest.fixture
+def calc():
+    return Calculator()
+
+
+def test_add(calc):
+    assert add(1, 2) == 3
+    assert utils.clamp(calc.total(), 0) == 0
+
+
+class TestCalculator:
+    def test_total(self, calc):
+        assert calc.total() == 0
+
+
+def make_calculator():
+    return Calculator()
+"#;
+        let result = parse(code, "python");
+        assert_eq!(
+            tests(&result),
+            vec![
+                ("calc", vec!["Calculator"]),
+                ("test_add", vec!["add", "utils.clamp"]),
+                ("test_total", vec!["Calculator"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jest_go_and_java_tests() {
+        let code = r#"import { describe, it, expect } from 'vitest';
+import * as fmt from '../src/format';
+import { parseConfig } from '../src/config';
+
+export function load() {
+  return parseConfig('');
+}
+
+describe('parseConfig', () => {
+  it('reads keys', () => {
+    expect(parseConfig('a=1').a).toBe(1);
+    expect(fmt.pretty({})).toBe('');
+  });
+});
+"#;
+        let result = parse(code, "typescript");
+        let load = result.chunks.iter().find(|c| c.start_line == 5).unwrap();
+        assert!(!load.is_test);
+        let suite = result.chunks.iter().find(|c| c.is_test).unwrap();
+        assert_eq!(suite.test_subjects, vec!["parseConfig", "fmt.pretty"]);
+
+        let code = r#"package calc
+
+import "testing"
+
+func TestAdd(t *testing.T) {
+	if Add(1, 2) != 3 {
+		t.Errorf("bad")
+	}
+}
+
+func Testify() {}
+"#;
+        assert_eq!(tests(&parse(code, "go")), vec![("TestAdd", vec!["Add"])]);
+
+        let code = r#"import org.junit.jupiter.api.Test;
+import static org.junit.jupiter.api.Assertions.*;
+
+class CalculatorTest {
+    @Test
+    void adds() {
+        Calculator calc = new Calculator();
+        assertEquals(3, calc.add(1, 2));
+    }
+}
+"#;
+        let result = parse(code, "java");
+        assert_eq!(tests(&result), vec![("adds", vec!["Calculator"])]);
+        assert!(result.chunks.iter().all(|c| c.is_test));
+    }
+
+    #[test]
+    fn test_find_tests_for_a_symbol() {
+        let files = [
+            (
+                "src/config.ts",
+                "export function parseConfig(text: string) {\n  return {};\n}\n\nexport function unused() {}\n",
+            ),
+            (
+                "test/config.test.ts",
+                "import { parseConfig } from '../src/config';\n\ntest('parses', () => {\n  parseConfig('a=1');\n});\n",
+            ),
+        ];
+        let results: Vec<(&str, ParseResult)> = files
+            .iter()
+            .map(|(path, code)| (*path, parse(code, "typescript")))
+            .collect();
+        let inputs: Vec<(&str, &ParseResult)> = results.iter().map(|(path, result)| (*path, result)).collect();
+        let table = SymbolTable::build(&inputs, &ResolveOptions::default());
+
+        let parse_config = &table.lookup("parseConfig")[0];
+        let found: Vec<(String, usize, usize)> = table
+            .find_tests(parse_config)
+            .into_iter()
+            .map(|t| (t.file, t.start_line, t.end_line))
+            .collect();
+        assert_eq!(found, vec![("test/config.test.ts".to_string(), 1, 5)]);
+
+        let unused = &table.lookup("unused")[0];
+        assert!(table.find_tests(unused).is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(all(test, feature = "bpe"))]
+mod tests {
+    use prism_indexer::{
+        split_large_chunk_with, tokenizer_for, BpeTokenizer, HeuristicTokenizer, PrismError, PrismParser, Tokenizer,
+    };
+
+    #[test]
+    fn test_bpe_counts() {
+        let cl100k = tokenizer_for("cl100k").unwrap();
+        assert_eq!(cl100k.name(), "cl100k");
+        assert_eq!(cl100k.count_tokens(""), 0);
+        assert_eq!(cl100k.count_tokens("hello world"), 2);
+        assert_eq!(cl100k.count_tokens("const userAccountRepository = createRepository();"), 8);
+        assert_eq!(HeuristicTokenizer.count_tokens("const userAccountRepository = createRepository();"), 12);
+
+        // Special tokens in code are counted as text
+        assert!(cl100k.count_tokens("<|endoftext|>") > 1);
+
+        // Llama 3's vocabulary is loaded rather than bundled
+        assert!(matches!(tokenizer_for("llama3"), Err(PrismError::TokenizerError(_))));
+        assert!(tokenizer_for("gpt2").is_err());
+
+        // "a", "b", " " and "ab", in tiktoken format
+        let ranks = "YQ== 0\nYg== 1\nIA== 2\nYWI= 3\n";
+        let custom = BpeTokenizer::from_tiktoken("tiny", ranks).unwrap();
+        assert_eq!(custom.count_tokens("ab ab"), 3);
+        assert!(BpeTokenizer::from_tiktoken("broken", "not base64!\n").is_err());
+    }
+
+    #[test]
+    fn test_parser_tokenizer_selection() {
+        let code = "export function greet(name: string) {\n  return `Hello, ${name}!`;\n}\n";
+
+        let mut parser = PrismParser::new("typescript").unwrap();
+        assert_eq!(parser.tokenizer(), "cl100k");
+        let chunk = parser.parse(code).unwrap().chunks.remove(0);
+        assert_eq!(chunk.tokens, tokenizer_for("cl100k").unwrap().count_tokens(&chunk.text));
+
+        parser.set_tokenizer("heuristic").unwrap();
+        let chunk = parser.parse(code).unwrap().chunks.remove(0);
+        assert_eq!(chunk.tokens, chunk.text.len() / 4);
+
+        assert!(parser.set_tokenizer("unknown").is_err());
+        assert_eq!(parser.tokenizer(), "heuristic");
+    }
+
+    #[test]
+    fn test_split_by_token_budget() {
+        let body: String = (0..30).map(|i| format!("  const value{} = compute({});\n", i, i)).collect();
+        let code = format!("function long() {{\n{}}}\n", body);
+        let mut parser = PrismParser::new("javascript").unwrap();
+        let chunk = parser.parse(&code).unwrap().chunks.remove(0);
+
+        let cl100k = tokenizer_for("cl100k").unwrap();
+        let parts = split_large_chunk_with(&chunk, 50, cl100k.as_ref());
+        assert!(parts.len() > 1);
+        for part in &parts {
+            assert_eq!(part.tokens, cl100k.count_tokens(&part.text));
+            assert!(part.tokens <= 50);
+        }
+    }
+}
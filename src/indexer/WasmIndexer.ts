@@ -82,8 +82,8 @@
  * SUPPORTED LANGUAGES:
  *
  * TypeScript/JavaScript (.ts, .tsx, .js, .jsx):
- * - Tree-sitter grammar: tree-sitter-typescript
- * - Features: Classes, functions, interfaces, types
+ * - Tree-sitter grammar: tree-sitter-typescript (tsx grammar for .tsx/.jsx)
+ * - Features: Classes, functions, interfaces, types, JSX components
  * - Status: Production-ready
  *
  * Python (.py):
@@ -106,6 +106,11 @@
  * - Features: Classes, methods, interfaces
  * - Status: Basic support
  *
+ * C/C++ (.c, .h, .cc, .cpp, .hpp):
+ * - Tree-sitter grammars: tree-sitter-c, tree-sitter-cpp
+ * - Features: Functions, classes/structs, namespaces, #include
+ * - Status: Basic support (headers are parsed as C++)
+ *
  * FUTURE ENHANCEMENTS:
  *
  * 1. CHUNKING IN RUST
@@ -145,9 +150,12 @@ import type {
 } from '../core/types/index.js';
 import type {
   ParseResult,
+  WASMCodeChunk,
   ChunkOptions,
+  ChunkingOptions,
   IndexOptions,
   LanguageDetection,
+  TokenizerName,
 } from './types.js';
 import { createPrismError, ErrorCode } from '../core/types/index.js';
 import * as fs from 'fs/promises';
@@ -158,13 +166,21 @@ import * as path from 'path';
  */
 const LANGUAGE_MAP: Record<string, string> = {
   '.ts': 'typescript',
-  '.tsx': 'typescript',
+  '.tsx': 'tsx',
   '.js': 'javascript',
-  '.jsx': 'javascript',
+  '.jsx': 'jsx',
   '.py': 'python',
   '.rs': 'rust',
   '.go': 'go',
   '.java': 'java',
+  '.c': 'c',
+  '.h': 'cpp',
+  '.cc': 'cpp',
+  '.cpp': 'cpp',
+  '.cxx': 'cpp',
+  '.hh': 'cpp',
+  '.hpp': 'cpp',
+  '.hxx': 'cpp',
 };
 
 /**
@@ -224,7 +240,7 @@ export class WasmIndexer implements IIndexer {
     const language = this.detectLanguage(filePath);
 
     // Parse the file
-    const result = await this.parseFile(content, language);
+    const result = await this.parseFile(content, language, filePath);
 
     // Convert ParseResult to CodeChunk[]
     return this.convertToCodeChunks(result, filePath);
@@ -282,12 +298,23 @@ export class WasmIndexer implements IIndexer {
 
   /**
    * Parse a file's content
+   *
+   * With a file path, chunk ids are deterministic per file, so re-indexing
+   * unchanged code reproduces the same ids. Invalid chunking options are
+   * rejected.
    */
-  async parseFile(content: string, language: string): Promise<ParseResult> {
+  async parseFile(
+    content: string,
+    language: string,
+    filePath?: string,
+    options?: ChunkingOptions
+  ): Promise<ParseResult> {
     this.ensureInitialized();
 
     try {
-      const result = this.wasm.parse_code(content, language);
+      const result = filePath
+        ? this.wasm.parse_file(content, language, filePath, options)
+        : this.wasm.parse_code(content, language, options);
       return result as ParseResult;
     } catch (error) {
       throw createPrismError(
@@ -322,9 +349,15 @@ export class WasmIndexer implements IIndexer {
         content: wasmChunk.text,
         language: wasmChunk.language,
         metadata: {
-          exports: wasmChunk.functions.filter(f => f.is_exported).map(f => f.name),
+          exports: [
+            ...wasmChunk.classes.filter(c => c.is_exported).map(c => c.name),
+            ...wasmChunk.functions.filter(f => f.is_exported).map(f => f.name),
+          ],
           imports: wasmChunk.imports.map(imp => imp.source),
           dependencies: wasmChunk.dependencies,
+          stableId: wasmChunk.stable_id,
+          contentHash: wasmChunk.content_hash,
+          overlap: wasmChunk.overlap ?? undefined,
         },
       });
     }
@@ -332,6 +365,14 @@ export class WasmIndexer implements IIndexer {
     return chunks;
   }
 
+  /**
+   * Count the tokens of some text as a model would
+   */
+  countTokens(text: string, tokenizer: TokenizerName = 'heuristic'): number {
+    this.ensureInitialized();
+    return this.wasm.count_tokens(text, tokenizer);
+  }
+
   /**
    * Get supported languages
    */
@@ -403,9 +444,15 @@ export class WasmIndexer implements IIndexer {
         content: wasmChunk.text,
         language: wasmChunk.language,
         metadata: {
-          exports: wasmChunk.functions.filter(f => f.is_exported).map(f => f.name),
+          exports: [
+            ...wasmChunk.classes.filter(c => c.is_exported).map(c => c.name),
+            ...wasmChunk.functions.filter(f => f.is_exported).map(f => f.name),
+          ],
           imports: wasmChunk.imports.map(imp => imp.source),
           dependencies: wasmChunk.dependencies,
+          stableId: wasmChunk.stable_id,
+          contentHash: wasmChunk.content_hash,
+          overlap: wasmChunk.overlap ?? undefined,
         },
       };
     });
@@ -430,24 +477,29 @@ export class WasmIndexer implements IIndexer {
   }
 
   /**
-   * Infer the kind of chunk based on content
+   * Map the chunk's symbol kind (from Rust) onto a chunk kind
    */
-  private inferChunkKind(chunk: any): CodeChunk['kind'] {
-    if (chunk.classes && chunk.classes.length > 0) {
-      return 'class';
-    }
-
-    if (chunk.functions && chunk.functions.length > 0) {
-      return 'function';
-    }
-
-    // Check if it's imports/exports only
-    if (chunk.imports && chunk.imports.length > 0 &&
-        chunk.text.split('\n').length < 20) {
-      return 'imports';
+  private inferChunkKind(chunk: WASMCodeChunk): CodeChunk['kind'] {
+    switch (chunk.kind) {
+      case 'function':
+        return 'function';
+      case 'method':
+        return 'method';
+      case 'class':
+      case 'struct':
+      case 'enum':
+        return 'class';
+      case 'interface':
+      case 'trait':
+      case 'type_alias':
+        return 'interface';
+      case 'constant':
+      case 'module':
+        return 'variable';
     }
 
-    return 'function'; // Default
+    // Chunks without a symbol: top-level statements and imports
+    return 'variable';
   }
 
   /**
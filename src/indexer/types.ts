@@ -24,6 +24,15 @@ export interface ParseResult {
 
   /** Extracted classes */
   classes: ClassInfo[];
+
+  /** Imports of the whole file */
+  imports: ImportInfo[];
+
+  /** Exports and re-exports of the whole file */
+  exports: ExportInfo[];
+
+  /** Top-level symbols of the document outline */
+  symbols: SymbolInfo[];
 }
 
 /**
@@ -31,9 +40,18 @@ export interface ParseResult {
  * This is the raw chunk format returned by the WASM module
  */
 export interface WASMCodeChunk {
-  /** Unique chunk identifier */
+  /** Deterministic chunk identifier; changes when the file, symbol or normalized content does */
   id: string;
 
+  /** Identity of the chunk's symbol within its file, kept across edits and moves */
+  stable_id: string;
+
+  /**
+   * Hash of the normalized content, kept across moves and reformatting;
+   * lines repeated from the previous chunk (`overlap`) are left out
+   */
+  content_hash: string;
+
   /** Chunk text content */
   text: string;
 
@@ -49,6 +67,15 @@ export interface WASMCodeChunk {
   /** Programming language */
   language: string;
 
+  /** Kind of the main symbol in this chunk, if any */
+  kind?: SymbolKind | null;
+
+  /** Qualified name of the main symbol in this chunk, if any */
+  qualified_name?: string | null;
+
+  /** Documentation of the main symbol in this chunk, if any */
+  documentation?: Documentation | null;
+
   /** Functions in this chunk */
   functions: FunctionInfo[];
 
@@ -60,22 +87,91 @@ export interface WASMCodeChunk {
 
   /** Dependencies extracted from chunk */
   dependencies: string[];
+
+  /** Calls made within this chunk */
+  calls: CallSite[];
+
+  /** Names declared in this chunk, with occurrence counts */
+  defines: IdentifierCount[];
+
+  /** Names used in this chunk without being declared there */
+  references: IdentifierCount[];
+
+  /** Whether most of this chunk is test code */
+  is_test: boolean;
+
+  /** Names of the symbols this chunk's tests exercise */
+  test_subjects: string[];
+
+  /** Whether this chunk's lines contain syntax errors; its boundaries may come from error recovery */
+  has_errors: boolean;
+
+  /**
+   * Leading lines repeated from the end of the previous chunk, when this
+   * chunk is a piece of a larger one; skip them when joining adjacent chunks
+   */
+  overlap?: LineRange | null;
 }
 
 /**
- * Error node information
+ * An inclusive, 1-based range of lines
+ */
+export interface LineRange {
+  start_line: number;
+  end_line: number;
+}
+
+/**
+ * Whether an identifier names a type, a value or a member
+ */
+export type IdentifierKind = 'type' | 'value' | 'property';
+
+/**
+ * An identifier and how often it occurs in a chunk
+ */
+export interface IdentifierCount {
+  /** Identifier as written */
+  name: string;
+
+  /** Type, value or property name */
+  kind: IdentifierKind;
+
+  /** Number of occurrences */
+  count: number;
+}
+
+/**
+ * A syntax error found while parsing
  */
 export interface ErrorNode {
-  /** Error message */
+  /** What went wrong and where, e.g. `Missing "}" in class body` */
   message: string;
 
   /** Location in source */
   location: SourceLocation;
 
-  /** Text at error location */
+  /** First line of the unparsed source, truncated; empty for missing tokens */
   text: string;
+
+  /** How serious the error is */
+  severity: Severity;
+
+  /** Kind of the construct being parsed (`arguments`, `class_body`) */
+  parent_kind?: string | null;
+
+  /** Token or node the parser inserted to recover */
+  missing?: string | null;
+
+  /** Tokens the grammar accepts at the error, when there are only a few */
+  expected: string[];
 }
 
+/**
+ * How serious a diagnostic is: `warning` for separators the parser
+ * inserted without losing structure
+ */
+export type Severity = 'error' | 'warning';
+
 /**
  * Source location
  */
@@ -100,6 +196,9 @@ export interface FunctionInfo {
   /** Function name */
   name: string;
 
+  /** 'function' or 'method' */
+  kind: SymbolKind;
+
   /** Function signature */
   signature: string;
 
@@ -109,8 +208,8 @@ export interface FunctionInfo {
   /** Ending line number */
   end_line: number;
 
-  /** Parameter names */
-  parameters: string[];
+  /** Parameters with types and defaults */
+  parameters: Parameter[];
 
   /** Return type if available */
   return_type?: string;
@@ -120,6 +219,148 @@ export interface FunctionInfo {
 
   /** Is this exported? */
   is_exported: boolean;
+
+  /** Declared or conventional visibility */
+  visibility: Visibility;
+
+  /** Is this a JSX-returning component? */
+  is_component: boolean;
+
+  /** Number of enclosing functions and types (0 at top level) */
+  depth: number;
+
+  /** Qualified name of the enclosing function or type */
+  enclosing_symbol?: string | null;
+
+  /** Doc comment or docstring */
+  documentation?: Documentation | null;
+
+  /** Calls made in the body, excluding named nested functions */
+  calls: CallSite[];
+
+  /** Test, fixture or hook, or declared inside a test module, class or block */
+  is_test: boolean;
+
+  /** Names of the symbols a test exercises, resolvable from its file */
+  test_subjects: string[];
+
+  /** Size and complexity of the function */
+  metrics: ComplexityMetrics;
+}
+
+/**
+ * Size and complexity of a function
+ */
+export interface ComplexityMetrics {
+  /** McCabe complexity: 1 plus branches, loops, cases, handlers and `&&`/`||` */
+  cyclomatic: number;
+
+  /** Control flow weighted by nesting, as defined by SonarSource */
+  cognitive: number;
+
+  /** Deepest nesting of control structures */
+  max_nesting: number;
+
+  /** Statements in the body */
+  statements: number;
+
+  /** Parameters, not counting `self`/`this` receivers */
+  parameters: number;
+
+  /** Lines with code, excluding blank and comment-only lines */
+  lines_of_code: number;
+}
+
+/**
+ * A call made from a function or chunk
+ */
+export interface CallSite {
+  /** Callee expression as written ('this.store.get', 'Vec::new') */
+  callee: string;
+
+  /** Simple name used to look the callee up ('get', 'new') */
+  name: string;
+
+  /** Receiver or path the callee is reached through */
+  receiver?: string | null;
+
+  /** Line number of the call */
+  line: number;
+
+  /** Column of the call (0-based) */
+  column: number;
+
+  /** Awaited call (`await f()`, Rust `f().await`) */
+  is_awaited: boolean;
+}
+
+/**
+ * Visibility of a declaration, normalised across languages
+ */
+export type Visibility = 'public' | 'protected' | 'internal' | 'private';
+
+/**
+ * Kind of a declared symbol, aligned with LSP SymbolKind
+ */
+export type SymbolKind =
+  | 'class'
+  | 'interface'
+  | 'struct'
+  | 'enum'
+  | 'trait'
+  | 'type_alias'
+  | 'constant'
+  | 'module'
+  | 'method'
+  | 'function';
+
+/**
+ * A node of the document symbol outline
+ */
+export interface SymbolInfo {
+  /** Stable id from kind and qualified name, e.g. 'method:Calculator.add' */
+  id: string;
+
+  /** Symbol name */
+  name: string;
+
+  /** Name including enclosing scopes, e.g. 'Calculator.add' */
+  qualified_name: string;
+
+  /** Symbol kind */
+  kind: SymbolKind;
+
+  /** Starting line number */
+  start_line: number;
+
+  /** Ending line number */
+  end_line: number;
+
+  /** Id of the enclosing symbol, if any */
+  parent_id?: string | null;
+
+  /** Members, nested functions and inner types */
+  children: SymbolInfo[];
+}
+
+/**
+ * Function parameter
+ */
+export interface Parameter {
+  /** Parameter name (or the full pattern when destructured) */
+  name: string;
+
+  /** Declared type if available */
+  type_annotation?: string;
+
+  /** Default value expression if available */
+  default_value?: string;
+
+  /** Is this a rest/variadic parameter? */
+  is_variadic: boolean;
+
+  /** Is this optional (or defaulted)? */
+  is_optional: boolean;
 }
 
 /**
@@ -129,10 +370,13 @@ export interface ClassInfo {
   /** Class name */
   name: string;
 
-  /** Base class if extends */
+  /** Class, interface, struct, enum or trait */
+  kind: SymbolKind;
+
+  /** Primary base class if extends */
   extends?: string;
 
-  /** Implemented interfaces */
+  /** Other supertypes: interfaces, mixins, embedded types, traits */
   implements: string[];
 
   /** Methods */
@@ -143,25 +387,133 @@ export interface ClassInfo {
 
   /** Ending line number */
   end_line: number;
+
+  /** Is this exported? */
+  is_exported: boolean;
+
+  /** Declared or conventional visibility */
+  visibility: Visibility;
+
+  /** Number of enclosing functions and types (0 at top level) */
+  depth: number;
+
+  /** Qualified name of the enclosing function or type */
+  enclosing_symbol?: string | null;
+
+  /** Doc comment or docstring */
+  documentation?: Documentation | null;
+}
+
+/**
+ * Documentation attached to a declaration
+ */
+export interface Documentation {
+  /** Line where the doc comment or docstring starts */
+  start_line: number;
+
+  /** Description without comment markers or tags */
+  text: string;
+
+  /** Documented parameters (@param, :param:) */
+  params: DocParam[];
+
+  /** Return value description (@returns, :returns:) */
+  returns?: string | null;
+
+  /** Thrown errors, type first (@throws, :raises:) */
+  throws: string[];
+}
+
+/**
+ * A documented parameter
+ */
+export interface DocParam {
+  /** Parameter name */
+  name: string;
+
+  /** Documented type if available */
+  type_annotation?: string | null;
+
+  /** Parameter description */
+  description: string;
+}
+
+/**
+ * How a module is imported
+ */
+export type ImportKind = 'static' | 'require' | 'dynamic';
+
+/**
+ * Shape of an import binding or export
+ */
+export type BindingKind = 'default' | 'named' | 'namespace' | 'wildcard';
+
+/**
+ * A single name introduced by an import
+ */
+export interface ImportBinding {
+  /** Name in the source module ('default', or '*' for namespace/wildcard) */
+  imported: string;
+
+  /** Name bound in this file ('*' for wildcard imports) */
+  local: string;
+
+  /** Binding shape */
+  kind: BindingKind;
+
+  /** Erased at compile time (`import { type A }`), so not a runtime edge */
+  is_type_only: boolean;
 }
 
 /**
  * Import information
  */
 export interface ImportInfo {
-  /** Source module/path */
+  /** Module specifier as written ('./utils', '..models', 'std::io') */
   source: string;
 
-  /** Imported names */
+  /** Local names the import binds */
   imported_names: string[];
 
-  /** Is this a type-only import? */
+  /** One entry per name the import introduces */
+  bindings: ImportBinding[];
+
+  /** Static import, require() or dynamic import() */
+  kind: ImportKind;
+
+  /** Python relative import level (number of leading dots) */
+  relative_level: number;
+
+  /** Whole statement is erased (`import type`, Python `if TYPE_CHECKING:`) */
   is_type_only: boolean;
 
   /** Import location */
   location: SourceLocation;
 }
 
+/**
+ * A name a module makes available to importers
+ */
+export interface ExportInfo {
+  /** Name importers see ('default', or '*' for wildcard re-exports) */
+  name: string;
+
+  /** Local name, or the name in `source` for re-exports */
+  local?: string | null;
+
+  /** Module the name is re-exported from */
+  source?: string | null;
+
+  /** Default, named, namespace (`export * as ns`) or wildcard (`export *`) */
+  kind: BindingKind;
+
+  /** `export type`, `export interface`, `export { type A }` */
+  is_type_only: boolean;
+
+  /** Export location */
+  location: SourceLocation;
+}
+
 /**
  * Options for chunking
  */
@@ -182,6 +534,37 @@ export interface ChunkOptions {
   include_imports?: boolean;
 }
 
+/**
+ * Options the WASM chunker splits code with
+ *
+ * Unset fields take the defaults of the file's language.
+ */
+export interface ChunkingOptions {
+  /** Size of the pieces an oversized chunk is split into, in tokens */
+  target_tokens?: number | null;
+
+  /** Chunks larger than this are split; defaults to 1000, or `target_tokens` if larger */
+  max_tokens?: number | null;
+
+  /** Tokens shared by consecutive pieces of a split chunk or of long top-level code */
+  overlap_tokens?: number | null;
+
+  /** Top-level code shorter than this is not chunked on its own */
+  min_lines?: number | null;
+
+  /** Top-level code and preprocessor blocks are split at this many lines */
+  max_lines?: number | null;
+
+  /** Attach the file's imports to every chunk */
+  include_imports?: boolean | null;
+
+  /** Start chunks at the doc comment of their definition */
+  include_docs?: boolean | null;
+
+  /** Whether nested functions and classes get their own chunks */
+  nested?: 'include' | 'exclude' | null;
+}
+
 /**
  * Options for indexing
  */
@@ -213,6 +596,15 @@ export interface LanguageDetection {
   extension: string;
 }
 
+/**
+ * Tokenizer that chunk token counts are measured with
+ *
+ * `heuristic` estimates ~4 bytes per token. `cl100k` matches OpenAI models
+ * and is only in builds with the `bpe` feature, as are vocabularies of other
+ * models, such as Llama 3, loaded with the parser's `set_tokenizer_vocabulary`.
+ */
+export type TokenizerName = 'cl100k' | 'heuristic';
+
 /**
  * Chunking strategy
  */